@@ -4,6 +4,8 @@ extern crate test;
 
 use std::{fs::File, io::Read};
 
+use anyhow::Error;
+use aoc2023_day10b::pipes::{self, Direction, Position, Tile, Tiles};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -13,26 +15,28 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
+    println!("{}", result);
+
+    Ok(())
 }
 
-fn run(content: &str) -> usize {
-    let tiles = parse(content);
+fn run(content: &str) -> Result<usize, Error> {
+    let tiles = pipes::parse(content)?;
 
     let start = get_starting_position(&tiles);
     let length = walk(&tiles, start);
 
-    length / 2
+    Ok(length / 2)
 }
 
 fn get_starting_position(tiles: &Tiles) -> Position {
@@ -132,83 +136,6 @@ fn get_connecting(tiles: &Tiles, Position { x, y }: &Position) -> (Direction, Po
     }
 }
 
-type Tiles = Vec<Vec<Tile>>;
-
-#[derive(Debug)]
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-#[derive(Debug, PartialEq, Eq)]
-enum Tile {
-    Empty,
-    Start,
-    Horizontal,
-    Vertical,
-    UpRight,
-    RightDown,
-    DownLeft,
-    LeftUp,
-}
-
-impl Tile {
-    fn connects(&self, dir: Direction) -> bool {
-        match dir {
-            Direction::Up => {
-                [Self::Start, Self::Vertical, Self::UpRight, Self::LeftUp].contains(self)
-            }
-            Direction::Right => [
-                Self::Start,
-                Self::Horizontal,
-                Self::UpRight,
-                Self::RightDown,
-            ]
-            .contains(self),
-            Direction::Down => {
-                [Self::Start, Self::Vertical, Self::RightDown, Self::DownLeft].contains(self)
-            }
-            Direction::Left => {
-                [Self::Start, Self::Horizontal, Self::DownLeft, Self::LeftUp].contains(self)
-            }
-        }
-    }
-}
-
-#[derive(Debug)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-// Parsing
-
-fn parse(content: &str) -> Tiles {
-    content.lines().map(parse_line).collect()
-}
-
-fn parse_line(line: &str) -> Vec<Tile> {
-    line.chars().map(Tile::from).collect()
-}
-
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
-        match value {
-            'S' => Self::Start,
-            '.' => Self::Empty,
-            '-' => Self::Horizontal,
-            '|' => Self::Vertical,
-            'L' => Self::UpRight,
-            'F' => Self::RightDown,
-            '7' => Self::DownLeft,
-            'J' => Self::LeftUp,
-            _ => unreachable!("should not be contained in the input"),
-        }
-    }
-}
-
 // testing
 #[cfg(test)]
 mod tests {
@@ -223,7 +150,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 8)
     }
 
@@ -234,7 +161,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 6831)
     }
 
@@ -0,0 +1,127 @@
+use anyhow::{anyhow, Error};
+
+pub type Tiles = Vec<Vec<Tile>>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Tile {
+    Empty,
+    Start,
+    Horizontal,
+    Vertical,
+    UpRight,
+    RightDown,
+    DownLeft,
+    LeftUp,
+}
+
+impl Tile {
+    pub fn connects(&self, dir: Direction) -> bool {
+        match dir {
+            Direction::Up => {
+                [Self::Start, Self::Vertical, Self::UpRight, Self::LeftUp].contains(self)
+            }
+            Direction::Right => [
+                Self::Start,
+                Self::Horizontal,
+                Self::UpRight,
+                Self::RightDown,
+            ]
+            .contains(self),
+            Direction::Down => {
+                [Self::Start, Self::Vertical, Self::RightDown, Self::DownLeft].contains(self)
+            }
+            Direction::Left => {
+                [Self::Start, Self::Horizontal, Self::DownLeft, Self::LeftUp].contains(self)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+// Parsing
+
+// A rectangle of tiles with exactly one start
+pub fn parse(content: &str) -> Result<Tiles, Error> {
+    let tiles: Tiles = content.lines().map(parse_line).collect::<Result<_, _>>()?;
+
+    let width = tiles.first().map(Vec::len).unwrap_or(0);
+    if width == 0 || tiles.iter().any(|row| row.len() != width) {
+        return Err(anyhow!("tiles should form a non empty rectangle"));
+    }
+
+    let starts = tiles
+        .iter()
+        .flatten()
+        .filter(|tile| **tile == Tile::Start)
+        .count();
+    if starts != 1 {
+        return Err(anyhow!(
+            "there should be one starting tile, found {}",
+            starts
+        ));
+    }
+
+    Ok(tiles)
+}
+
+fn parse_line(line: &str) -> Result<Vec<Tile>, Error> {
+    line.chars().map(Tile::try_from).collect()
+}
+
+impl TryFrom<char> for Tile {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'S' => Ok(Self::Start),
+            '.' => Ok(Self::Empty),
+            '-' => Ok(Self::Horizontal),
+            '|' => Ok(Self::Vertical),
+            'L' => Ok(Self::UpRight),
+            'F' => Ok(Self::RightDown),
+            '7' => Ok(Self::DownLeft),
+            'J' => Ok(Self::LeftUp),
+            _ => Err(anyhow!("unexpected character in input: {}", value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("S.\n.").is_err());
+        assert!(parse("S.\n.S").is_err());
+        assert!(parse("..\n..").is_err());
+        assert!(parse("S.\n.x").is_err());
+
+        let tiles = parse(".S\nF7").unwrap();
+        assert_eq!(tiles[0][1], Tile::Start);
+        assert!(tiles[1][0].connects(Direction::Right));
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            let _ = parse(&content);
+        }
+    }
+}
@@ -4,107 +4,69 @@ extern crate test;
 
 use std::{fs::File, io::Read};
 
+use anyhow::Error;
+use aoc2023_day11b::universe::Universe;
 use clap::Parser;
-use iter_tools::Itertools;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[arg(short, long)]
     file: String,
-}
 
-fn main() {
-    let args = Args::parse();
-    let file = args.file;
+    /// Number of rows or columns every empty one is replaced with
+    #[arg(short, long, default_value_t = 1000000)]
+    expansion: u64,
 
-    let mut file = File::open(file).unwrap();
-    let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    /// Also print the closest pair of galaxies
+    #[arg(long)]
+    closest: bool,
 
-    let result = run(&content);
+    /// Also print the farthest pair of galaxies
+    #[arg(long)]
+    farthest: bool,
 
-    println!("{}", result)
+    /// Print the distance between two galaxies, numbered from 1 in reading order
+    #[arg(short, long, num_args = 2, value_names = ["A", "B"])]
+    between: Option<Vec<usize>>,
 }
 
-fn run(content: &str) -> usize {
-    let galaxies = parse(content);
-    let (mty_cols, mty_rows) = get_empty(&galaxies);
-
-    galaxies
-        .into_iter()
-        .combinations(2)
-        .map(|galaxies| {
-            compute_distance(
-                galaxies.try_into().expect("we know the length"),
-                &mty_cols,
-                &mty_rows,
-            )
-        })
-        .sum()
-}
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let file = args.file;
 
-type Galaxies = Vec<(usize, usize)>;
+    let mut file = File::open(file)?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
 
-fn compute_distance(
-    galaxies: [(usize, usize); 2],
-    mty_cols: &[usize],
-    mty_rows: &[usize],
-) -> usize {
-    let (x1, y1) = galaxies[0];
-    let (x2, y2) = galaxies[1];
+    let universe = Universe::parse(&content, args.expansion)?;
 
-    let (x1, x2) = if x1 > x2 { (x2, x1) } else { (x1, x2) };
-    let (y1, y2) = if y1 > y2 { (y2, y1) } else { (y1, y2) };
+    if let Some(ids) = args.between {
+        println!("{}", universe.distance(ids[0], ids[1])?);
+        return Ok(());
+    }
 
-    y2.abs_diff(y1)
-        + x2.abs_diff(x1)
-        + 999999 * mty_cols.iter().filter(|yc| y1 < **yc && **yc < y2).count()
-        + 999999 * mty_rows.iter().filter(|xr| x1 < **xr && **xr < x2).count()
-}
+    if args.closest {
+        if let Some((distance, a, b)) = universe.closest() {
+            println!("closest: galaxies {} and {} at {}", a, b, distance);
+        }
+    }
+    if args.farthest {
+        if let Some((distance, a, b)) = universe.farthest() {
+            println!("farthest: galaxies {} and {} at {}", a, b, distance);
+        }
+    }
 
-fn get_empty(galaxies: &Galaxies) -> (Vec<usize>, Vec<usize>) {
-    let height = *galaxies
-        .iter()
-        .map(|(_, y)| y)
-        .max()
-        .expect("galaxies should be non-empty");
-    let mty_cols = (0..height)
-        .filter(|yc| galaxies.iter().all(|(_, yg)| yc != yg))
-        .collect();
-
-    let width = *galaxies
-        .iter()
-        .map(|(x, _)| x)
-        .max()
-        .expect("galaxies should be non-empty");
-    let mty_rows = (0..width)
-        .filter(|xr| galaxies.iter().all(|(xg, _)| xr != xg))
-        .collect();
-
-    (mty_cols, mty_rows)
-}
+    println!("{}", universe.total_distance()?);
 
-// Parsing
-
-fn parse(content: &str) -> Galaxies {
-    content
-        .lines()
-        .enumerate()
-        .map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter(|(_, c)| *c == '#')
-                .map(move |(x, _)| (x, y))
-        })
-        .flatten()
-        .collect()
+    Ok(())
 }
 
 // testing
 #[cfg(test)]
 mod tests {
     use ::test::Bencher;
+    use aoc2023_day11b::universe;
 
     use super::*;
 
@@ -115,7 +77,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = universe::run(&content, 1000000).unwrap();
         assert_eq!(result, 82000210)
     }
 
@@ -126,7 +88,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = universe::run(&content, 1000000).unwrap();
         assert_eq!(result, 597714117556)
     }
 
@@ -137,6 +99,6 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content));
+        b.iter(|| universe::run(&content, 1000000));
     }
 }
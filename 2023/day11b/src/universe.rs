@@ -0,0 +1,259 @@
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, Error};
+
+pub type Position = (u64, u64);
+
+// Largest expanded coordinate, so a distance between two galaxies and the diagonals of
+// `farthest` fit in an i64
+const MAX_COORDINATE: u64 = i64::MAX as u64 / 2;
+
+// Galaxy positions after every empty row and column has been replaced by `factor` of them,
+// in reading order, so galaxy `i` of the puzzle is at index `i - 1`
+#[derive(Debug)]
+pub struct Universe {
+    pub galaxies: Vec<Position>,
+}
+
+impl Universe {
+    pub fn parse(content: &str, factor: u64) -> Result<Self, Error> {
+        if factor == 0 {
+            return Err(anyhow!("expansion factor should be at least 1"));
+        }
+
+        let galaxies: Vec<(usize, usize)> = content
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect();
+
+        let columns = expansion(galaxies.iter().map(|(x, _)| *x), factor)?;
+        let rows = expansion(galaxies.iter().map(|(_, y)| *y), factor)?;
+
+        let galaxies = galaxies
+            .into_iter()
+            .map(|(x, y)| (columns[x], rows[y]))
+            .collect();
+
+        Ok(Universe { galaxies })
+    }
+
+    pub fn galaxy(&self, id: usize) -> Result<Position, Error> {
+        id.checked_sub(1)
+            .and_then(|i| self.galaxies.get(i))
+            .copied()
+            .ok_or(anyhow!(
+                "there is no galaxy {}, ids go from 1 to {}",
+                id,
+                self.galaxies.len()
+            ))
+    }
+
+    pub fn distance(&self, a: usize, b: usize) -> Result<u64, Error> {
+        Ok(manhattan(self.galaxy(a)?, self.galaxy(b)?))
+    }
+
+    // Both axes are independent, and on a sorted axis the coordinate at index i is
+    // subtracted from the i coordinates after it and added for the i before it
+    pub fn total_distance(&self) -> Result<u64, Error> {
+        let axis = |mut coordinates: Vec<u64>| -> Option<u64> {
+            coordinates.sort_unstable();
+            coordinates
+                .iter()
+                .enumerate()
+                .try_fold((0u64, 0u64), |(total, prefix), (i, coordinate)| {
+                    let distances = coordinate.checked_mul(i as u64)? - prefix;
+                    Some((total.checked_add(distances)?, prefix + coordinate))
+                })
+                .map(|(total, _)| total)
+        };
+
+        axis(self.galaxies.iter().map(|(x, _)| *x).collect())
+            .zip(axis(self.galaxies.iter().map(|(_, y)| *y).collect()))
+            .and_then(|(x, y)| x.checked_add(y))
+            .ok_or(anyhow!("total distance overflows"))
+    }
+
+    // Sweep from left to right keeping the galaxies that are horizontally closer than the
+    // best distance so far, ordered by row. Returns the distance and both galaxy ids.
+    pub fn closest(&self) -> Option<(u64, usize, usize)> {
+        let mut order: Vec<usize> = (0..self.galaxies.len()).collect();
+        order.sort_by_key(|i| self.galaxies[*i]);
+
+        let mut best: Option<(u64, usize, usize)> = None;
+        let mut active = BTreeSet::new();
+        let mut left = 0;
+
+        for &i in &order {
+            let (x, y) = self.galaxies[i];
+            let limit = best.map(|(distance, _, _)| distance).unwrap_or(u64::MAX);
+
+            while x - self.galaxies[order[left]].0 >= limit {
+                let (lx, ly) = self.galaxies[order[left]];
+                active.remove(&(ly, lx, order[left]));
+                left += 1;
+            }
+
+            let range =
+                (y.saturating_sub(limit), 0, 0)..=(y.saturating_add(limit), u64::MAX, usize::MAX);
+            for &(oy, ox, j) in active.range(range) {
+                let distance = manhattan((x, y), (ox, oy));
+                if distance < best.map(|(best, _, _)| best).unwrap_or(u64::MAX) {
+                    best = Some((distance, i.min(j) + 1, i.max(j) + 1));
+                }
+            }
+
+            active.insert((y, x, i));
+        }
+
+        best
+    }
+
+    // The farthest pair in manhattan distance maximizes either x + y or x - y
+    pub fn farthest(&self) -> Option<(u64, usize, usize)> {
+        if self.galaxies.len() < 2 {
+            return None;
+        }
+
+        let diagonals: [fn(Position) -> i64; 2] =
+            [|(x, y)| x as i64 + y as i64, |(x, y)| x as i64 - y as i64];
+
+        diagonals
+            .iter()
+            .filter_map(|diagonal| {
+                let key = |i: &usize| diagonal(self.galaxies[*i]);
+                let min = (0..self.galaxies.len()).min_by_key(key)?;
+                let max = (0..self.galaxies.len()).max_by_key(key)?;
+                let distance = manhattan(self.galaxies[min], self.galaxies[max]);
+                Some((distance, min.min(max) + 1, min.max(max) + 1))
+            })
+            .max_by_key(|(distance, _, _)| *distance)
+    }
+}
+
+// Sum of the distances between every pair of galaxies once the universe expanded
+pub fn run(content: &str, factor: u64) -> Result<u64, Error> {
+    Universe::parse(content, factor)?.total_distance()
+}
+
+// Expanded coordinate of every index along one axis, given the occupied indices
+fn expansion(occupied: impl Iterator<Item = usize>, factor: u64) -> Result<Vec<u64>, Error> {
+    let mut used = vec![];
+    for i in occupied {
+        if used.len() <= i {
+            used.resize(i + 1, false);
+        }
+        used[i] = true;
+    }
+
+    let mut positions = Vec::with_capacity(used.len());
+    let mut position: u64 = 0;
+    for used in used {
+        if position > MAX_COORDINATE {
+            return Err(anyhow!(
+                "universe expands past {}, use a smaller factor",
+                MAX_COORDINATE
+            ));
+        }
+        positions.push(position);
+        position = position.saturating_add(if used { 1 } else { factor });
+    }
+
+    Ok(positions)
+}
+
+fn manhattan((x1, y1): Position, (x2, y2): Position) -> u64 {
+    x1.abs_diff(x2) + y1.abs_diff(y2)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs::File, io::Read};
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn universe(factor: u64) -> Universe {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        Universe::parse(&content, factor).unwrap()
+    }
+
+    fn brute_force(universe: &Universe) -> Vec<(u64, usize, usize)> {
+        let n = universe.galaxies.len();
+        (1..=n)
+            .flat_map(|a| (a + 1..=n).map(move |b| (a, b)))
+            .map(|(a, b)| (universe.distance(a, b).unwrap(), a, b))
+            .collect()
+    }
+
+    #[test]
+    fn test_distances() {
+        let universe = universe(2);
+
+        assert_eq!(universe.distance(5, 9).unwrap(), 9);
+        assert_eq!(universe.distance(1, 7).unwrap(), 15);
+        assert_eq!(universe.distance(3, 6).unwrap(), 17);
+        assert_eq!(universe.distance(8, 9).unwrap(), 5);
+        assert!(universe.distance(0, 1).is_err());
+        assert!(universe.distance(1, 10).is_err());
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert!(Universe::parse("#.#", u64::MAX).is_err());
+        assert!(Universe::parse("#.#", MAX_COORDINATE).is_err());
+        assert!(Universe::parse("#.#", MAX_COORDINATE - 1).is_ok());
+
+        // Nine pairs across the one wide column
+        let universe = Universe::parse("#.#\n#.#\n#.#", MAX_COORDINATE - 1).unwrap();
+        assert!(universe.total_distance().is_err());
+    }
+
+    #[test]
+    fn test_totals() {
+        for factor in [1, 2, 10, 100] {
+            let universe = universe(factor);
+            let pairs = brute_force(&universe);
+
+            let total: u64 = pairs.iter().map(|(distance, _, _)| distance).sum();
+            assert_eq!(universe.total_distance().unwrap(), total);
+
+            let closest = pairs.iter().map(|(distance, _, _)| *distance).min();
+            assert_eq!(universe.closest().map(|(distance, _, _)| distance), closest);
+
+            let farthest = pairs.iter().map(|(distance, _, _)| *distance).max();
+            assert_eq!(
+                universe.farthest().map(|(distance, _, _)| distance),
+                farthest
+            );
+
+            for (distance, a, b) in [universe.closest(), universe.farthest()]
+                .into_iter()
+                .flatten()
+            {
+                assert_eq!(universe.distance(a, b).unwrap(), distance);
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*", factor in any::<u64>()) {
+            if let Ok(universe) = Universe::parse(&content, factor) {
+                let _ = universe.total_distance();
+                let _ = universe.closest();
+                let _ = universe.farthest();
+            }
+        }
+    }
+}
@@ -2,207 +2,245 @@
 
 extern crate test;
 
-use std::{collections::HashMap, fs::File, io::Read, str::FromStr, usize};
+use std::{fs::File, io::Read};
 
-use anyhow::{anyhow, Error};
+use anyhow::Error;
+use aoc2023_day12b::record::{self, render, Condition, Record};
 use clap::Parser;
-use iter_tools::Itertools;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[arg(short, long)]
     file: String,
+    #[arg(long, default_value_t = 5)]
+    folds: usize,
+    /// Print up to this many valid arrangements per record
+    #[arg(short, long)]
+    enumerate: Option<usize>,
+    /// Print this many uniformly sampled arrangements per record
+    #[arg(short, long)]
+    sample: Option<usize>,
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
+
+    if args.enumerate.is_some() || args.sample.is_some() {
+        let mut rng = StdRng::seed_from_u64(args.seed);
 
-    let result = run(&content);
+        for record in record::parse(&content)? {
+            let record = record.unfold(args.folds);
+            let arrangements = Arrangements::new(&record);
 
-    println!("{}", result)
+            println!("{} ({})", record, arrangements.count());
+            if let Some(limit) = args.enumerate {
+                for arrangement in arrangements.enumerate(limit) {
+                    println!("  {}", arrangement);
+                }
+            }
+            if let Some(samples) = args.sample {
+                for _ in 0..samples {
+                    if let Some(arrangement) = arrangements.sample(&mut rng) {
+                        println!("  {}", arrangement);
+                    }
+                }
+            }
+        }
+    }
+
+    let result = run(&content, args.folds)?;
+
+    println!("{}", result);
+
+    Ok(())
 }
 
-fn run(content: &str) -> usize {
-    let records = parse(content);
+fn run(content: &str, folds: usize) -> Result<usize, Error> {
+    let records = record::parse(content)?;
 
-    records
+    Ok(records
         .into_iter()
-        .map(|record| process(&record.conditions, &record.groups))
-        .inspect(|s| println!("\nprocessed one: {}\n", s))
-        .sum()
+        .map(|record| Arrangements::new(&record.unfold(folds)).count())
+        .sum())
 }
 
-type Cache = HashMap<(usize, usize), usize>;
-
-fn process(conditions: &[Condition], groups: &[usize]) -> usize {
-    let mut cache = HashMap::new();
-    if conditions.contains(&Condition::Operational) {
-        split_chunks(conditions, groups, &mut cache)
-    } else {
-        recurse_conditions(conditions, groups, &mut cache)
-    }
+// Number of ways to complete a record from every state (position, group index, length of
+// the current run of damaged springs), stored in a dense table
+struct Arrangements<'a> {
+    record: &'a Record,
+    runs: usize,
+    ways: Vec<usize>,
 }
 
-fn recurse_conditions(conditions: &[Condition], groups: &[usize], cache: &mut Cache) -> usize {
-    fn inner(conditions: &[Condition], groups: &[usize], cache: &mut Cache) -> usize {
-        if groups.is_empty() {
-            if conditions.iter().any(|c| *c == Condition::Damaged) {
-                0
-            } else {
-                1
+impl<'a> Arrangements<'a> {
+    fn new(record: &'a Record) -> Self {
+        let positions = record.conditions.len() + 1;
+        let groups = record.groups.len() + 1;
+        let runs = record.groups.iter().copied().max().unwrap_or(0) + 1;
+
+        let mut arrangements = Arrangements {
+            record,
+            runs,
+            ways: vec![0; positions * groups * runs],
+        };
+
+        let last = record.conditions.len();
+        for group in 0..groups {
+            for run in 0..runs {
+                let done = (group == record.groups.len() && run == 0)
+                    || (group + 1 == record.groups.len() && run == record.groups[group]);
+                arrangements.set(last, group, run, done as usize);
             }
-        } else {
-            match conditions {
-                [] => 0, // groups are non-empty at this point
-                [Condition::Damaged, rest @ ..] => {
-                    let first_group = groups[0] - 1;
-                    if first_group > rest.len()
-                        || (first_group < rest.len() && rest[first_group] != Condition::Unknown)
-                    {
-                        0
-                    } else if first_group == rest.len() {
-                        if groups.len() == 1 {
-                            1
-                        } else {
-                            0
-                        }
-                    } else {
-                        recurse_conditions(&rest[first_group + 1..], &groups[1..], cache)
-                    }
-                }
-                [Condition::Unknown, rest @ ..] => {
-                    let mut conditions_damaged = vec![Condition::Damaged];
-                    conditions_damaged.extend_from_slice(rest);
+        }
 
-                    recurse_conditions(&conditions_damaged, groups, cache)
-                        + recurse_conditions(rest, groups, cache)
-                }
-                [Condition::Operational, ..] => {
-                    unreachable!("the first field should never be operational at this point")
+        for position in (0..last).rev() {
+            for group in 0..groups {
+                for run in 0..runs {
+                    let ways = [Condition::Operational, Condition::Damaged]
+                        .into_iter()
+                        .filter_map(|spring| arrangements.next(position, group, run, spring))
+                        .map(|(group, run)| arrangements.get(position + 1, group, run))
+                        .sum();
+                    arrangements.set(position, group, run, ways);
                 }
             }
         }
+
+        arrangements
     }
 
-    let key = (conditions.len(), groups.len());
-    let cached = cache.get(&key);
-    match cached {
-        Some(res) => *res,
-        None => {
-            let res = inner(conditions, groups, cache);
-            cache.insert(key, res);
-            res
-        }
+    fn index(&self, position: usize, group: usize, run: usize) -> usize {
+        (position * (self.record.groups.len() + 1) + group) * self.runs + run
     }
-}
 
-fn split_chunks(conditions: &[Condition], groups: &[usize], cache: &mut Cache) -> usize {
-    let condition_chunks: Vec<&[Condition]> = conditions
-        .split(|c| *c == Condition::Operational)
-        .filter(|chunk| !chunk.is_empty())
-        .collect();
-
-    let mut res = 0;
-    on_all_k_splits(
-        &mut vec![],
-        groups,
-        condition_chunks.len(),
-        &mut |group_split| {
-            res += condition_chunks
-                .iter()
-                .zip(group_split.iter())
-                .map(|(conditions, groups)| recurse_conditions(conditions, groups, cache))
-                .product::<usize>()
-        },
-    );
+    fn get(&self, position: usize, group: usize, run: usize) -> usize {
+        self.ways[self.index(position, group, run)]
+    }
 
-    res
-}
+    fn set(&mut self, position: usize, group: usize, run: usize, ways: usize) {
+        let index = self.index(position, group, run);
+        self.ways[index] = ways;
+    }
 
-// based on:
-// https://stackoverflow.com/questions/62486128/how-to-iterate-over-all-possible-partitions-of-a-slice-non-empty-subslices
-fn on_all_k_splits<'a, F>(head: &mut Vec<&'a [usize]>, rest: &'a [usize], k: usize, f: &mut F)
-where
-    F: FnMut(&[&[usize]]),
-{
-    if k == 1 {
-        head.push(rest);
-        f(head);
-        head.pop();
-    } else {
-        for i in 0..=rest.len() {
-            let (next, tail) = rest.split_at(i);
-            head.push(next);
-            on_all_k_splits(head, tail, k - 1, f);
-            head.pop();
+    // State after placing `spring` at `position`, if it is allowed there
+    fn next(
+        &self,
+        position: usize,
+        group: usize,
+        run: usize,
+        spring: Condition,
+    ) -> Option<(usize, usize)> {
+        let condition = self.record.conditions[position];
+        if condition != Condition::Unknown && condition != spring {
+            return None;
+        }
+
+        let groups = &self.record.groups;
+        match spring {
+            Condition::Operational if run == 0 => Some((group, 0)),
+            Condition::Operational if group < groups.len() && run == groups[group] => {
+                Some((group + 1, 0))
+            }
+            Condition::Damaged if group < groups.len() && run < groups[group] => {
+                Some((group, run + 1))
+            }
+            _ => None,
         }
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Condition {
-    Operational,
-    Damaged,
-    Unknown,
-}
+    fn count(&self) -> usize {
+        self.get(0, 0, 0)
+    }
 
-#[derive(Debug, Hash)]
-struct Record {
-    conditions: Vec<Condition>,
-    groups: Vec<usize>,
-}
+    // Choices with a non-zero number of completions from `position` on
+    fn choices(&self, position: usize, group: usize, run: usize) -> Vec<(Condition, usize, usize)> {
+        [Condition::Operational, Condition::Damaged]
+            .into_iter()
+            .filter_map(|spring| {
+                self.next(position, group, run, spring)
+                    .map(|(group, run)| (spring, group, run))
+            })
+            .filter(|(_, group, run)| self.get(position + 1, *group, *run) > 0)
+            .collect()
+    }
 
-// Parsing
+    fn enumerate(&self, limit: usize) -> Vec<String> {
+        let mut arrangements = vec![];
 
-fn parse(content: &str) -> Vec<Record> {
-    content
-        .lines()
-        .map(Record::from_str)
-        .collect::<Result<_, _>>()
-        .expect("someting went wrong during parsing")
-}
+        let mut stack = vec![(0, 0, 0, vec![])];
+        while let Some((position, group, run, springs)) = stack.pop() {
+            if arrangements.len() == limit {
+                break;
+            }
 
-impl FromStr for Record {
-    type Err = Error;
+            if position == self.record.conditions.len() {
+                if self.get(position, group, run) > 0 {
+                    arrangements.push(render(&springs));
+                }
+                continue;
+            }
 
-    fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let folds = 5;
+            for (spring, group, run) in self.choices(position, group, run).into_iter().rev() {
+                let mut springs = springs.clone();
+                springs.push(spring);
+                stack.push((position + 1, group, run, springs));
+            }
+        }
 
-        let mut parts = line.split_ascii_whitespace();
+        arrangements
+    }
 
-        let condition_part = parts.next().expect("we know it has two parts");
-        let condition_part = (0..folds).map(|_| condition_part).join("?");
-        let conditions = condition_part
-            .chars()
-            .map(|c| match c {
-                '.' => Ok(Condition::Operational),
-                '#' => Ok(Condition::Damaged),
-                '?' => Ok(Condition::Unknown),
-                _ => Err(anyhow!("unexpected character in input: {}", c)),
-            })
-            .collect::<Result<_, _>>()?;
+    // Picks each spring with probability proportional to the number of completions,
+    // which makes every valid arrangement equally likely
+    fn sample(&self, rng: &mut impl Rng) -> Option<String> {
+        if self.count() == 0 {
+            return None;
+        }
 
-        let group_part = parts.next().expect("we know it has two parts");
-        let group_part = (0..folds).map(|_| group_part).join(",");
-        let groups = group_part
-            .split(",")
-            .map(usize::from_str)
-            .collect::<Result<_, _>>()?;
+        let (mut group, mut run) = (0, 0);
+        let mut springs = vec![];
 
-        Ok(Record { conditions, groups })
+        for position in 0..self.record.conditions.len() {
+            let choices = self.choices(position, group, run);
+            let total: usize = choices
+                .iter()
+                .map(|(_, group, run)| self.get(position + 1, *group, *run))
+                .sum();
+
+            let mut pick = rng.gen_range(0..total);
+            for (spring, next_group, next_run) in choices {
+                let ways = self.get(position + 1, next_group, next_run);
+                if pick < ways {
+                    springs.push(spring);
+                    (group, run) = (next_group, next_run);
+                    break;
+                }
+                pick -= ways;
+            }
+        }
+
+        Some(render(&springs))
     }
 }
 
 // testing
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use ::test::Bencher;
+    use aoc2023_day12b_old::brute;
+    use iter_tools::Itertools;
+    use proptest::{collection::vec, prelude::*};
 
     use super::*;
 
@@ -213,8 +251,106 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
-        assert_eq!(result, 21)
+        let result = run(&content, 1).unwrap();
+        assert_eq!(result, 21);
+
+        let result = run(&content, 5).unwrap();
+        assert_eq!(result, 525152)
+    }
+
+    #[test]
+    fn test_arrangements() {
+        let record = Record::from_str("?###???????? 3,2,1").unwrap();
+        let arrangements = Arrangements::new(&record);
+
+        let all = arrangements.enumerate(usize::MAX);
+        assert_eq!(all.len(), arrangements.count());
+        assert_eq!(all[0], ".###....##.#");
+        assert_eq!(all[9], ".###.##.#...");
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let sample = arrangements.sample(&mut rng).unwrap();
+            assert!(all.contains(&sample));
+        }
+
+        let unfolded = record.unfold(5);
+        assert_eq!(Arrangements::new(&unfolded).count(), 506250);
+    }
+
+    // Records of up to `len` springs in any condition, most of them without arrangements
+    fn record(len: usize) -> impl Strategy<Value = Record> {
+        let springs = vec(prop_oneof![Just('.'), Just('#'), Just('?')], 1..len);
+        (springs, vec(1usize..4, 1..4)).prop_map(|(springs, groups)| {
+            let line = format!("{} {}", String::from_iter(springs), groups.iter().join(","));
+            line.parse().unwrap()
+        })
+    }
+
+    // Records with at least one arrangement, a valid one with some springs hidden
+    fn solvable() -> impl Strategy<Value = Record> {
+        let groups = vec(1usize..4, 1..5);
+        let gaps = vec(0usize..3, 6);
+        (groups, gaps, vec(any::<bool>(), 30)).prop_map(|(groups, gaps, hidden)| {
+            let mut springs = ".".repeat(gaps[0]);
+            for (i, group) in groups.iter().enumerate() {
+                if i > 0 {
+                    springs += &".".repeat(gaps[i] + 1);
+                }
+                springs += &"#".repeat(*group);
+            }
+            springs += &".".repeat(gaps[5]);
+
+            let springs: String = springs
+                .chars()
+                .zip(hidden.iter().cycle())
+                .map(|(spring, hidden)| if *hidden { '?' } else { spring })
+                .collect();
+            format!("{} {}", springs, groups.iter().join(","))
+                .parse()
+                .unwrap()
+        })
+    }
+
+    fn brute_force(record: &Record) -> usize {
+        let damaged = record
+            .conditions
+            .iter()
+            .map(|condition| match condition {
+                Condition::Operational => Some(false),
+                Condition::Damaged => Some(true),
+                Condition::Unknown => None,
+            })
+            .collect();
+        brute::process_rec(&damaged, &record.groups)
+    }
+
+    proptest! {
+        #[test]
+        fn test_against_brute_force(record in record(14)) {
+            prop_assert_eq!(Arrangements::new(&record).count(), brute_force(&record));
+        }
+
+        #[test]
+        fn test_unfolded(record in record(6)) {
+            let unfolded = record.unfold(2);
+            prop_assert_eq!(Arrangements::new(&unfolded).count(), brute_force(&unfolded));
+        }
+
+        #[test]
+        fn test_solvable(record in solvable()) {
+            let arrangements = Arrangements::new(&record);
+            let all = arrangements.enumerate(usize::MAX);
+
+            prop_assert!(arrangements.count() >= 1);
+            prop_assert_eq!(all.len(), arrangements.count());
+            for arrangement in all {
+                let complete: Record = format!("{} {}", arrangement, record.groups.iter().join(","))
+                    .parse()
+                    .unwrap();
+                prop_assert_eq!(Arrangements::new(&complete).count(), 1);
+            }
+        }
     }
 
     #[test]
@@ -224,8 +360,11 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
-        assert_eq!(result, 8180)
+        let result = run(&content, 1).unwrap();
+        assert_eq!(result, 8180);
+
+        let result = run(&content, 5).unwrap();
+        assert_eq!(result, 620189727003627)
     }
 
     #[bench]
@@ -235,6 +374,6 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content));
+        b.iter(|| run(&content, 5));
     }
 }
@@ -0,0 +1,102 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Error};
+use iter_tools::Itertools;
+
+pub fn render(conditions: &[Condition]) -> String {
+    conditions
+        .iter()
+        .map(|condition| match condition {
+            Condition::Operational => '.',
+            Condition::Damaged => '#',
+            Condition::Unknown => '?',
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Condition {
+    Operational,
+    Damaged,
+    Unknown,
+}
+
+#[derive(Debug, Hash)]
+pub struct Record {
+    pub conditions: Vec<Condition>,
+    pub groups: Vec<usize>,
+}
+
+// Parsing
+
+pub fn parse(content: &str) -> Result<Vec<Record>, Error> {
+    content.lines().map(Record::from_str).collect()
+}
+
+impl Record {
+    pub fn unfold(&self, folds: usize) -> Record {
+        let conditions = (0..folds)
+            .map(|_| self.conditions.clone())
+            .collect_vec()
+            .join(&Condition::Unknown);
+        let groups = self.groups.repeat(folds);
+
+        Record { conditions, groups }
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            render(&self.conditions),
+            self.groups.iter().join(",")
+        )
+    }
+}
+
+impl FromStr for Record {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut parts = line.split_ascii_whitespace();
+
+        let condition_part = parts
+            .next()
+            .ok_or(anyhow!("record should have springs and groups: {}", line))?;
+        let conditions = condition_part
+            .chars()
+            .map(|c| match c {
+                '.' => Ok(Condition::Operational),
+                '#' => Ok(Condition::Damaged),
+                '?' => Ok(Condition::Unknown),
+                _ => Err(anyhow!("unexpected character in input: {}", c)),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let group_part = parts
+            .next()
+            .ok_or(anyhow!("record should have springs and groups: {}", line))?;
+        let groups = group_part
+            .split(",")
+            .map(usize::from_str)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Record { conditions, groups })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            let _ = parse(&content);
+        }
+    }
+}
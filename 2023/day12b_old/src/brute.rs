@@ -0,0 +1,76 @@
+// Tries both conditions of every unknown spring, pruning prefixes that already
+// contradict the groups
+pub fn process_rec(damaged: &Vec<Option<bool>>, groups: &Vec<usize>) -> usize {
+    let complete = damaged.iter().all(|d| *d != None);
+
+    if !grouping_possible(damaged, groups, complete) {
+        return 0;
+    }
+
+    if complete {
+        return 1;
+    }
+
+    let (next_false, next_true) = create_reucursion_damaged(damaged);
+    process_rec(&next_false, groups) + process_rec(&next_true, groups)
+}
+
+fn grouping_possible(damaged: &Vec<Option<bool>>, groups: &Vec<usize>, complete: bool) -> bool {
+    let mut curr_groups = vec![];
+    let mut curr_group = 0;
+
+    let mut last_incomplete = damaged.len() == 0;
+    for d in damaged {
+        match d {
+            None => {
+                if curr_group > 0 {
+                    curr_groups.push(curr_group);
+                    curr_group = 0;
+                    last_incomplete = true;
+                }
+                break;
+            }
+            Some(true) => curr_group += 1,
+            Some(false) => {
+                if curr_group > 0 {
+                    curr_groups.push(curr_group);
+                    curr_group = 0;
+                }
+            }
+        }
+    }
+    if curr_group > 0 {
+        curr_groups.push(curr_group);
+    }
+
+    let mut pairs = curr_groups.iter().zip(groups.iter()).peekable();
+    let mut res = !complete || curr_groups.len() == groups.len();
+    while let Some((c, g)) = pairs.next() {
+        res = res
+            && if last_incomplete && pairs.peek() == None {
+                c <= g
+            } else {
+                c == g
+            }
+    }
+
+    res
+}
+
+fn create_reucursion_damaged(
+    damaged: &Vec<Option<bool>>,
+) -> (Vec<Option<bool>>, Vec<Option<bool>>) {
+    let (index, _) = damaged
+        .iter()
+        .enumerate()
+        .find(|(_, d)| *d == &None)
+        .expect("We know that one element must be none");
+
+    let mut next_false = damaged.clone();
+    next_false[index] = Some(false);
+
+    let mut next_true = damaged.clone();
+    next_true[index] = Some(true);
+
+    (next_false, next_true)
+}
@@ -2,8 +2,10 @@
 
 extern crate test;
 
-use std::{collections::HashSet, fs::File, io::Read, usize};
+use std::{fs::File, io::Read};
 
+use anyhow::Error;
+use aoc2023_day13b::reflection;
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -11,139 +13,34 @@ use clap::Parser;
 struct Args {
     #[arg(short, long)]
     file: String,
+    #[arg(short, long, default_value_t = 0)]
+    smudges: u32,
+    #[arg(short, long)]
+    explain: bool,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
-
-    let result = run(&content);
-
-    println!("{}", result)
-}
-
-fn run(content: &str) -> usize {
-    let fields = parse(content);
-
-    fields.iter().map(Field::process).sum()
-}
-
-#[derive(Debug)]
-struct Field {
-    coordinates: Vec<(usize, usize)>,
-    height: usize,
-    width: usize,
-}
-
-impl Field {
-    fn process(&self) -> usize {
-        self.vertical_reflections() + 100 * self.horizontal_reflections()
-    }
-
-    fn vertical_reflections(&self) -> usize {
-        let cols = get_sets(
-            &self.coordinates,
-            Box::new(|(x, _)| x),
-            Box::new(|(_, y)| y),
-            self.width,
-        );
-
-        (0..self.width - 1)
-            .filter(|i| is_reflection(&cols, *i))
-            .map(|i| i + 1)
-            .sum()
-    }
-
-    fn horizontal_reflections(&self) -> usize {
-        let rows = get_sets(
-            &self.coordinates,
-            Box::new(|(_, y)| y),
-            Box::new(|(x, _)| x),
-            self.height,
-        );
-
-        (0..self.height - 1)
-            .filter(|i| is_reflection(&rows, *i))
-            .map(|i| i + 1)
-            .sum()
+    file.read_to_string(&mut content)?;
+
+    if args.explain {
+        for (i, pattern) in reflection::parse(&content)?.iter().enumerate() {
+            match pattern.reflection(args.smudges) {
+                Ok(reflection) => println!("{}: {}", i, reflection),
+                Err(err) => println!("{}: {}", i, err),
+            }
+        }
     }
-}
 
-type Coordinates = (usize, usize);
-type CoordinateProjection = Box<dyn Fn(&Coordinates) -> &usize>;
-
-fn get_sets(
-    coordinates: &Vec<Coordinates>,
-    filter_proj: CoordinateProjection,
-    map_proj: CoordinateProjection,
-    length: usize,
-) -> Vec<HashSet<usize>> {
-    (0..length)
-        .map(|i| {
-            coordinates
-                .iter()
-                .filter(|c| filter_proj(*c) == &i)
-                .map(|c| map_proj(c))
-                .cloned()
-                .collect()
-        })
-        .collect()
-}
+    let result = reflection::run(&content, args.smudges)?;
 
-fn is_reflection(sets: &Vec<HashSet<usize>>, i: usize) -> bool {
-    let hypothetical_len = 2 * (i + 1);
-    let (l, r) = if hypothetical_len <= sets.len() {
-        (0, hypothetical_len)
-    } else {
-        (2 * i + 2 - sets.len(), sets.len())
-    };
+    println!("{}", result);
 
-    is_symmetric(&sets[l..r])
-}
-
-fn is_symmetric(sets: &[HashSet<usize>]) -> bool {
-    let mid = sets.len() / 2 - 1;
-
-    (0..mid + 1).all(|i| sets[mid - i] == sets[mid + i + 1])
-}
-
-// Parsing
-
-fn parse(content: &str) -> Vec<Field> {
-    content
-        .lines()
-        .collect::<Vec<&str>>()
-        .split(|line| line.is_empty())
-        .map(Field::from)
-        .collect()
-}
-
-impl From<&[&str]> for Field {
-    fn from(lines: &[&str]) -> Self {
-        let height = lines.len();
-        let width = lines[0].len();
-
-        let coordinates = lines
-            .iter()
-            .enumerate()
-            .map(|(y, line)| {
-                line.chars()
-                    .enumerate()
-                    .filter_map(move |(x, c)| if c == '#' { Some((x, y)) } else { None })
-            })
-            .flatten()
-            .collect();
-
-        Field {
-            coordinates,
-            height,
-            width,
-        }
-    }
+    Ok(())
 }
 
 // testing
@@ -160,7 +57,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = reflection::run(&content, 0).unwrap();
         assert_eq!(result, 405)
     }
 
@@ -171,7 +68,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = reflection::run(&content, 0).unwrap();
         assert_eq!(result, 34821)
     }
 
@@ -182,6 +79,6 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content));
+        b.iter(|| reflection::run(&content, 0));
     }
 }
@@ -0,0 +1,240 @@
+use std::fmt::Display;
+
+use anyhow::{anyhow, Error};
+
+// Rows and columns of a pattern encoded as bitmasks of their rocks
+#[derive(Debug)]
+pub struct Pattern {
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Vertical,
+    Horizontal,
+}
+
+pub type Coordinates = (usize, usize);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Reflection {
+    pub axis: Axis,
+    // Number of columns left of or rows above the mirror
+    pub position: usize,
+    // Cells differing from their mirror image, along with that image
+    pub smudges: Vec<(Coordinates, Coordinates)>,
+}
+
+impl Reflection {
+    pub fn score(&self) -> usize {
+        match self.axis {
+            Axis::Vertical => self.position,
+            Axis::Horizontal => 100 * self.position,
+        }
+    }
+}
+
+impl Display for Reflection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let axis = match self.axis {
+            Axis::Vertical => "vertical",
+            Axis::Horizontal => "horizontal",
+        };
+        write!(f, "{} mirror after {}", axis, self.position)?;
+
+        for ((x, y), (mx, my)) in &self.smudges {
+            write!(f, ", smudge at ({}, {}) mirrors ({}, {})", x, y, mx, my)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Pattern {
+    // The unique mirror line for which exactly `smudges` cells differ from their image
+    pub fn reflection(&self, smudges: u32) -> Result<Reflection, Error> {
+        let candidates = [Axis::Vertical, Axis::Horizontal]
+            .into_iter()
+            .flat_map(|axis| {
+                let lines = self.lines(axis);
+                (1..lines.len())
+                    .filter(move |position| differences(lines, *position) == smudges)
+                    .map(move |position| (axis, position))
+            })
+            .collect::<Vec<_>>();
+
+        match candidates.as_slice() {
+            [(axis, position)] => Ok(Reflection {
+                axis: *axis,
+                position: *position,
+                smudges: self.smudges(*axis, *position),
+            }),
+            [] => Err(anyhow!(
+                "no mirror line with {} smudges in pattern:\n{}",
+                smudges,
+                self
+            )),
+            _ => Err(anyhow!(
+                "{} mirror lines with {} smudges in pattern:\n{}",
+                candidates.len(),
+                smudges,
+                self
+            )),
+        }
+    }
+
+    // Lines perpendicular to the mirror, columns for a vertical one
+    fn lines(&self, axis: Axis) -> &[u64] {
+        match axis {
+            Axis::Vertical => &self.columns,
+            Axis::Horizontal => &self.rows,
+        }
+    }
+
+    fn smudges(&self, axis: Axis, position: usize) -> Vec<(Coordinates, Coordinates)> {
+        let lines = self.lines(axis);
+
+        mirrored_pairs(lines.len(), position)
+            .flat_map(|(i, j)| {
+                let diff = lines[i] ^ lines[j];
+                (0..u64::BITS as usize)
+                    .filter(move |bit| diff & (1 << bit) != 0)
+                    .map(move |bit| match axis {
+                        Axis::Vertical => ((i, bit), (j, bit)),
+                        Axis::Horizontal => ((bit, i), (bit, j)),
+                    })
+            })
+            .collect()
+    }
+}
+
+fn mirrored_pairs(len: usize, position: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..position.min(len - position)).map(move |i| (position - 1 - i, position + i))
+}
+
+fn differences(lines: &[u64], position: usize) -> u32 {
+    mirrored_pairs(lines.len(), position)
+        .map(|(i, j)| (lines[i] ^ lines[j]).count_ones())
+        .sum()
+}
+
+// Sum of the scores of every pattern, each mirrored with exactly `smudges` smudges
+pub fn run(content: &str, smudges: u32) -> Result<usize, Error> {
+    parse(content)?
+        .iter()
+        .map(|pattern| pattern.reflection(smudges).map(|r| r.score()))
+        .sum()
+}
+
+// Parsing
+
+// Patterns are separated by blank lines
+pub fn parse(content: &str) -> Result<Vec<Pattern>, Error> {
+    content
+        .lines()
+        .collect::<Vec<&str>>()
+        .split(|line| line.is_empty())
+        .map(Pattern::try_from)
+        .collect()
+}
+
+impl TryFrom<&[&str]> for Pattern {
+    type Error = Error;
+
+    fn try_from(lines: &[&str]) -> Result<Self, Self::Error> {
+        let height = lines.len();
+        let width = lines.first().map(|line| line.len()).unwrap_or(0);
+
+        if height > u64::BITS as usize || width > u64::BITS as usize {
+            return Err(anyhow!("pattern larger than {} cells", u64::BITS));
+        }
+
+        let mut rows = vec![0; height];
+        let mut columns = vec![0; width];
+
+        for (y, line) in lines.iter().enumerate() {
+            if line.len() != width {
+                return Err(anyhow!("ragged pattern line: {}", line));
+            }
+
+            for (x, c) in line.chars().enumerate() {
+                match c {
+                    '#' => {
+                        rows[y] |= 1 << x;
+                        columns[x] |= 1 << y;
+                    }
+                    '.' => (),
+                    _ => return Err(anyhow!("unexpected character in pattern: {}", c)),
+                }
+            }
+        }
+
+        Ok(Pattern { rows, columns })
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in &self.rows {
+            let line: String = (0..self.columns.len())
+                .map(|x| if row & (1 << x) != 0 { '#' } else { '.' })
+                .collect();
+            writeln!(f, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+
+    #[test]
+    fn test_smudge() {
+        let lines = [
+            "#.##..##.",
+            "..#.##.#.",
+            "##......#",
+            "##......#",
+            "..#.##.#.",
+            "..##..##.",
+            "#.#.##.#.",
+        ];
+        let pattern = Pattern::try_from(&lines[..]).unwrap();
+
+        let exact = pattern.reflection(0).unwrap();
+        assert_eq!(exact.axis, Axis::Vertical);
+        assert_eq!(exact.position, 5);
+        assert!(exact.smudges.is_empty());
+
+        let smudged = pattern.reflection(1).unwrap();
+        assert_eq!(smudged.axis, Axis::Horizontal);
+        assert_eq!(smudged.position, 3);
+        assert_eq!(smudged.smudges, vec![((0, 0), (0, 5))]);
+
+        assert!(pattern.reflection(20).is_err());
+    }
+
+    #[test]
+    fn test_multiple_lines() {
+        let lines = ["#..#", "#..#"];
+        let pattern = Pattern::try_from(&lines[..]).unwrap();
+
+        assert!(pattern.reflection(0).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(lines in vec("\\PC*", 0..20)) {
+            let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+            if let Ok(pattern) = Pattern::try_from(&lines[..]) {
+                let _ = pattern.reflection(0);
+                let _ = pattern.reflection(1);
+            }
+        }
+    }
+}
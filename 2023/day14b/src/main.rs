@@ -2,15 +2,10 @@
 
 extern crate test;
 
-use std::{
-    collections::HashSet,
-    fmt::{Display, Write},
-    fs::File,
-    i64,
-    io::Read,
-    usize,
-};
+use std::{collections::HashSet, fs::File, i64, io::Read, usize};
 
+use anyhow::Error;
+use aoc2023_day14b::platform::{self, Field};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -20,23 +15,25 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
+    println!("{}", result);
+
+    Ok(())
 }
 
-fn run(content: &str) -> i64 {
-    let field = parse(content);
+fn run(content: &str) -> Result<i64, Error> {
+    let field = platform::parse(content)?;
 
-    process(field)
+    Ok(process(field))
 }
 
 fn process(field: Field) -> i64 {
@@ -66,15 +63,6 @@ fn process(field: Field) -> i64 {
     field.score()
 }
 
-impl Field {
-    fn score(self) -> i64 {
-        self.rolling
-            .iter()
-            .map(|(_, y)| self.height as i64 - y)
-            .sum()
-    }
-}
-
 fn spin_cycle(field: &Field) -> Field {
     let field = roll_field(field, Direction::North);
     let field = roll_field(&field, Direction::West);
@@ -227,14 +215,6 @@ fn reconstruct_field_from_rows(
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct Field {
-    steady: HashSet<(i64, i64)>,
-    rolling: HashSet<(i64, i64)>,
-    height: usize,
-    width: usize,
-}
-
 #[derive(Debug, PartialEq, Eq)]
 struct Slice {
     steady: Vec<i64>,
@@ -250,68 +230,6 @@ enum Direction {
     East,
 }
 
-// Parsing
-
-fn parse(content: &str) -> Field {
-    let stones = content
-        .lines()
-        .enumerate()
-        .map(|(y, line)| {
-            line.chars().enumerate().filter_map(move |(x, c)| match c {
-                '#' => Some((false, (x, y))),
-                'O' => Some((true, (x, y))),
-                '.' => None,
-                _ => unreachable!("should not find this character in the input"),
-            })
-        })
-        .flatten()
-        .map(|(i, (x, y))| (i, (x as i64, y as i64)));
-
-    let steady = stones
-        .clone()
-        .filter_map(|(rolling, coordinates)| (!rolling).then_some(coordinates))
-        .collect();
-
-    let rolling = stones
-        .filter_map(|(rolling, coordinates)| rolling.then_some(coordinates))
-        .collect();
-
-    let height = content.lines().count();
-    let width = content.lines().next().expect("at least one line").len();
-
-    Field {
-        steady,
-        rolling,
-        height,
-        width,
-    }
-}
-
-// Printing
-
-impl Display for Field {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.height as i64 {
-            for x in 0..self.width as i64 {
-                let steady = self.steady.contains(&(x, y));
-                let rolling = self.rolling.contains(&(x, y));
-
-                let c = match (steady, rolling) {
-                    (true, false) => '#',
-                    (false, true) => 'O',
-                    (false, false) => '.',
-                    _ => unreachable!("should never happen"),
-                };
-
-                f.write_char(c)?
-            }
-            f.write_char('\n')?
-        }
-
-        Ok(())
-    }
-}
-
 // testing
 #[cfg(test)]
 mod tests {
@@ -328,7 +246,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 64)
     }
 
@@ -339,7 +257,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 104409)
     }
 
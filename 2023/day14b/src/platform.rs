@@ -0,0 +1,113 @@
+use std::{
+    collections::HashSet,
+    fmt::{Display, Write},
+};
+
+use anyhow::{anyhow, Error};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Field {
+    pub steady: HashSet<(i64, i64)>,
+    pub rolling: HashSet<(i64, i64)>,
+    pub height: usize,
+    pub width: usize,
+}
+
+impl Field {
+    // Load on the north support beams
+    pub fn score(self) -> i64 {
+        self.rolling
+            .iter()
+            .map(|(_, y)| self.height as i64 - y)
+            .sum()
+    }
+}
+
+// Parsing
+
+// A non empty rectangle of rounded rocks `O`, cube rocks `#` and empty spaces `.`
+pub fn parse(content: &str) -> Result<Field, Error> {
+    let mut steady = HashSet::new();
+    let mut rolling = HashSet::new();
+
+    let height = content.lines().count();
+    let width = content.lines().next().map(str::len).unwrap_or(0);
+    if width == 0 {
+        return Err(anyhow!("platform should not be empty"));
+    }
+
+    for (y, line) in content.lines().enumerate() {
+        if line.len() != width {
+            return Err(anyhow!("platform should be a rectangle: {}", line));
+        }
+
+        for (x, c) in line.chars().enumerate() {
+            let coordinates = (x as i64, y as i64);
+            match c {
+                '#' => steady.insert(coordinates),
+                'O' => rolling.insert(coordinates),
+                '.' => false,
+                _ => return Err(anyhow!("unexpected character in input: {}", c)),
+            };
+        }
+    }
+
+    Ok(Field {
+        steady,
+        rolling,
+        height,
+        width,
+    })
+}
+
+// Printing
+
+impl Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height as i64 {
+            for x in 0..self.width as i64 {
+                let steady = self.steady.contains(&(x, y));
+                let rolling = self.rolling.contains(&(x, y));
+
+                let c = match (steady, rolling) {
+                    (true, false) => '#',
+                    (false, true) => 'O',
+                    (false, false) => '.',
+                    _ => unreachable!("should never happen"),
+                };
+
+                f.write_char(c)?
+            }
+            f.write_char('\n')?
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let field = parse("O.#\n#O.\n").unwrap();
+        assert_eq!(field.to_string(), "O.#\n#O.\n");
+        assert_eq!(field.score(), 3);
+
+        assert!(parse("").is_err());
+        assert!(parse("O.#\n#O").is_err());
+        assert!(parse("O.#\n#Ox").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            if let Ok(field) = parse(&content) {
+                let _ = field.to_string();
+            }
+        }
+    }
+}
@@ -4,6 +4,7 @@ extern crate test;
 
 use std::{fs::File, io::Read};
 
+use aoc2023_day15b::hash;
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -21,35 +22,11 @@ fn main() {
     let mut content = String::new();
     file.read_to_string(&mut content).unwrap();
 
-    let result = run(&content);
+    let result = hash::run(&content);
 
     println!("{}", result)
 }
 
-fn run(content: &str) -> usize {
-    let parts = parse(content);
-
-    parts.map(process).sum()
-}
-
-fn process(part: &str) -> usize {
-    part.bytes().fold(0, |acc, c| {
-        let mut acc = acc;
-
-        acc += c as usize;
-        acc *= 17;
-        acc %= 256;
-
-        acc
-    })
-}
-
-// Parsing
-
-fn parse(content: &str) -> impl Iterator<Item = &str> {
-    content.trim().split(",")
-}
-
 // testing
 #[cfg(test)]
 mod tests {
@@ -66,7 +43,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = hash::run(&content);
         assert_eq!(result, 1320)
     }
 
@@ -77,7 +54,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = hash::run(&content);
         assert_eq!(result, 498538)
     }
 
@@ -88,6 +65,6 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content));
+        b.iter(|| hash::run(&content));
     }
 }
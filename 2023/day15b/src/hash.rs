@@ -0,0 +1,47 @@
+// Sum of the hashes of every step of the initialization sequence
+pub fn run(content: &str) -> usize {
+    parse(content).map(hash).sum()
+}
+
+// Running value of each byte plus the previous one, times 17, modulo 256
+pub fn hash(step: &str) -> usize {
+    step.bytes().fold(0, |acc, c| {
+        let mut acc = acc;
+
+        acc += c as usize;
+        acc *= 17;
+        acc %= 256;
+
+        acc
+    })
+}
+
+// Parsing
+
+// Comma separated steps, the newline at the end is not part of the last one
+pub fn parse(content: &str) -> impl Iterator<Item = &str> {
+    content.trim().split(',')
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash("HASH"), 52);
+        assert_eq!(hash(""), 0);
+        assert_eq!(parse("rn=1,cm-\n").collect::<Vec<_>>(), vec!["rn=1", "cm-"]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            for step in parse(&content) {
+                prop_assert!(hash(step) < 256);
+            }
+        }
+    }
+}
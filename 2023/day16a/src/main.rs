@@ -2,52 +2,92 @@
 
 extern crate test;
 
-use std::{fs::File, i64, io::Read, usize};
+use std::{fs::File, io::Read, thread, time::Duration};
 
-use clap::Parser;
+use anyhow::Error;
+use aoc2023_day16b::contraption::{self, Direction, Field, Tile, DIRECTIONS};
+use clap::{Parser, ValueEnum};
+use iter_tools::Itertools;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[arg(short, long)]
     file: String,
+    #[arg(short, default_value_t = 0)]
+    x: usize,
+    #[arg(short, default_value_t = 0)]
+    y: usize,
+    #[arg(short, long, value_enum, default_value_t = Direction::Right)]
+    direction: Direction,
+    #[arg(short, long, value_enum)]
+    render: Option<Render>,
+    #[arg(short, long)]
+    animate: bool,
+    #[arg(long, default_value_t = 50)]
+    delay: u64,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Render {
+    Energized,
+    Beams,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
+
+    let field = contraption::parse(&content)?;
+    let entry = ((args.x as i64, args.y as i64), args.direction);
+
+    if args.animate {
+        animate(&field, entry, Duration::from_millis(args.delay));
+    }
 
-    let result = run(&content);
+    let light = energize(&field, entry);
 
-    println!("{}", result)
+    match args.render {
+        Some(Render::Energized) => println!("{}\n", render_energized(&light)),
+        Some(Render::Beams) => println!("{}\n", render_beams(&field, &light)),
+        None => (),
+    }
+
+    let result = score(&light);
+
+    println!("{}", result);
+
+    Ok(())
 }
 
-fn run(content: &str) -> usize {
-    let field = parse(content);
+type State = Vec<Vec<[bool; 4]>>;
 
+fn energize(field: &Field, ((x, y), direction): ((i64, i64), Direction)) -> State {
     let mut light: State = (0..field.height)
         .map(|_| (0..field.width).map(|_| [false; 4]).collect())
         .collect();
 
-    walk(&field, &mut light, (0, 0), Direction::Right);
+    walk(field, &mut light, (x, y), direction);
 
     light
-        .into_iter()
+}
+
+fn score(light: &State) -> usize {
+    light
+        .iter()
         .map(|row| {
-            row.into_iter()
-                .map(|l| l.into_iter().any(|w| w))
+            row.iter()
+                .map(|l| l.iter().any(|w| *w))
                 .filter(|row| *row)
                 .count()
         })
         .sum()
 }
 
-type State = Vec<Vec<[bool; 4]>>;
-
 fn walk(field: &Field, state: &mut State, (x, y): (i64, i64), direction: Direction) {
     if x < 0 || x > field.width as i64 - 1 || y < 0 || y > field.height as i64 - 1 {
         return;
@@ -62,7 +102,13 @@ fn walk(field: &Field, state: &mut State, (x, y): (i64, i64), direction: Directi
 
     state[yi][xi][direction as usize] = true;
 
-    let steps = match field.tiles[yi][xi] {
+    for direction in exits(&field.tiles[yi][xi], direction) {
+        walk(field, state, direction.apply((x, y)), direction)
+    }
+}
+
+fn exits(tile: &Tile, direction: Direction) -> Vec<Direction> {
+    match tile {
         Tile::Empty => vec![direction],
         Tile::MirrorUp => vec![match direction {
             Direction::Right => Direction::Up,
@@ -84,70 +130,93 @@ fn walk(field: &Field, state: &mut State, (x, y): (i64, i64), direction: Directi
             Direction::Right | Direction::Left => vec![direction],
             Direction::Down | Direction::Up => vec![Direction::Left, Direction::Right],
         },
-    };
-
-    for direction in steps {
-        let (x, y) = match direction {
-            Direction::Right => (x + 1, y),
-            Direction::Down => (x, y + 1),
-            Direction::Left => (x - 1, y),
-            Direction::Up => (x, y - 1),
-        };
-
-        walk(field, state, (x, y), direction)
     }
 }
 
-#[derive(Debug)]
-struct Field {
-    tiles: Vec<Vec<Tile>>,
-    height: usize,
-    width: usize,
-}
-
-#[derive(Debug)]
-enum Tile {
-    Empty,
-    MirrorUp,
-    MirrorDown,
-    SplitHorizontal,
-    SplitVertical,
-}
+// Rendering
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Right,
-    Down,
-    Left,
-    Up,
+fn render_energized(light: &State) -> String {
+    light
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|l| if l.iter().any(|w| *w) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .join("\n")
 }
 
-// Parsing
-
-fn parse(content: &str) -> Field {
-    let tiles = content
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '.' => Tile::Empty,
-                    '/' => Tile::MirrorUp,
-                    '\\' => Tile::MirrorDown,
-                    '-' => Tile::SplitHorizontal,
-                    '|' => Tile::SplitVertical,
-                    _ => unreachable!(),
+// Mirrors and splitters are kept, empty tiles show the direction of the beam passing
+// through them or the number of beams crossing
+fn render_beams(field: &Field, light: &State) -> String {
+    field
+        .tiles
+        .iter()
+        .zip(light)
+        .map(|(tiles, row)| {
+            tiles
+                .iter()
+                .zip(row)
+                .map(|(tile, l)| {
+                    let directions = DIRECTIONS
+                        .into_iter()
+                        .filter(|direction| l[*direction as usize])
+                        .collect_vec();
+
+                    match (tile, directions.as_slice()) {
+                        (Tile::Empty, []) => '.',
+                        (Tile::Empty, [direction]) => direction.arrow(),
+                        (Tile::Empty, directions) => {
+                            char::from_digit(directions.len() as u32, 10).unwrap()
+                        }
+                        (tile, _) => tile.symbol(),
+                    }
                 })
-                .collect()
+                .collect::<String>()
         })
-        .collect();
+        .join("\n")
+}
 
-    let height = content.lines().count();
-    let width = content.lines().next().unwrap().len();
+// Advances the beam front one tile per frame until every beam has left the field or
+// runs along an already energized path
+fn animate(field: &Field, entry: ((i64, i64), Direction), delay: Duration) {
+    let mut light: State = (0..field.height)
+        .map(|_| (0..field.width).map(|_| [false; 4]).collect())
+        .collect();
 
-    Field {
-        tiles,
-        height,
-        width,
+    let mut front = vec![entry];
+
+    while !front.is_empty() {
+        front.retain(|((x, y), direction)| {
+            if *x < 0 || *x >= field.width as i64 || *y < 0 || *y >= field.height as i64 {
+                return false;
+            }
+
+            let seen = &mut light[*y as usize][*x as usize][*direction as usize];
+            !std::mem::replace(seen, true)
+        });
+
+        let mut frame = render_beams(field, &light)
+            .lines()
+            .map(|l| l.chars().collect_vec())
+            .collect_vec();
+        for ((x, y), _) in &front {
+            frame[*y as usize][*x as usize] = '@';
+        }
+
+        print!("\x1b[2J\x1b[H");
+        println!("{}", frame.into_iter().map(String::from_iter).join("\n"));
+        println!("\n{} energized", score(&light));
+        thread::sleep(delay);
+
+        front = front
+            .into_iter()
+            .flat_map(|((x, y), direction)| {
+                exits(&field.tiles[y as usize][x as usize], direction)
+                    .into_iter()
+                    .map(move |direction| (direction.apply((x, y)), direction))
+            })
+            .collect();
     }
 }
 
@@ -158,6 +227,14 @@ mod tests {
 
     use super::*;
 
+    fn run(content: &str) -> usize {
+        let field = contraption::parse(content).unwrap();
+
+        let light = energize(&field, ((0, 0), Direction::Right));
+
+        score(&light)
+    }
+
     #[test]
     fn test_short() {
         let file = "short_data";
@@ -169,6 +246,44 @@ mod tests {
         assert_eq!(result, 46)
     }
 
+    #[test]
+    fn test_render() {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        let field = contraption::parse(&content).unwrap();
+        let light = energize(&field, ((0, 0), Direction::Right));
+
+        assert_eq!(
+            render_beams(&field, &light),
+            ">|<<<\\....
+|v-.\\^....
+.v...|->>>
+.v...v^.|.
+.v...v^...
+.v...v^..\\
+.v../2\\\\..
+<->-/vv|..
+.|<<<2-|.\\
+.v//.|.v.."
+        );
+        assert_eq!(
+            render_energized(&light),
+            "######....
+.#...#....
+.#...#####
+.#...##...
+.#...##...
+.#...##...
+.#..####..
+########..
+.#######..
+.#...#.#.."
+        );
+    }
+
     #[test]
     fn test_long() {
         let file = "long_data";
@@ -0,0 +1,123 @@
+use anyhow::{anyhow, Error};
+use clap::ValueEnum;
+
+#[derive(Debug)]
+pub struct Field {
+    pub tiles: Vec<Vec<Tile>>,
+    pub height: usize,
+    pub width: usize,
+}
+
+#[derive(Debug)]
+pub enum Tile {
+    Empty,
+    MirrorUp,
+    MirrorDown,
+    SplitHorizontal,
+    SplitVertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+pub const DIRECTIONS: [Direction; 4] = [
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+    Direction::Up,
+];
+
+impl Tile {
+    pub fn symbol(&self) -> char {
+        match self {
+            Tile::Empty => '.',
+            Tile::MirrorUp => '/',
+            Tile::MirrorDown => '\\',
+            Tile::SplitHorizontal => '-',
+            Tile::SplitVertical => '|',
+        }
+    }
+}
+
+impl Direction {
+    pub fn apply(self, (x, y): (i64, i64)) -> (i64, i64) {
+        match self {
+            Direction::Right => (x + 1, y),
+            Direction::Down => (x, y + 1),
+            Direction::Left => (x - 1, y),
+            Direction::Up => (x, y - 1),
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Up => '^',
+        }
+    }
+}
+
+// Parsing
+
+// A non empty rectangle of tiles
+pub fn parse(content: &str) -> Result<Field, Error> {
+    let tiles: Vec<Vec<Tile>> = content
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| match c {
+                    '.' => Ok(Tile::Empty),
+                    '/' => Ok(Tile::MirrorUp),
+                    '\\' => Ok(Tile::MirrorDown),
+                    '-' => Ok(Tile::SplitHorizontal),
+                    '|' => Ok(Tile::SplitVertical),
+                    _ => Err(anyhow!("unexpected character in input: {}", c)),
+                })
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+
+    let height = tiles.len();
+    let width = tiles.first().map(Vec::len).unwrap_or(0);
+    if width == 0 || tiles.iter().any(|row| row.len() != width) {
+        return Err(anyhow!("tiles should form a non empty rectangle"));
+    }
+
+    Ok(Field {
+        tiles,
+        height,
+        width,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let field = parse(".|\n\\-\n").unwrap();
+        assert_eq!((field.width, field.height), (2, 2));
+        assert_eq!(field.tiles[1][0].symbol(), '\\');
+
+        assert!(parse("").is_err());
+        assert!(parse(".|\n\\").is_err());
+        assert!(parse(".|\n\\x").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            let _ = parse(&content);
+        }
+    }
+}
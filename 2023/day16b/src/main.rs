@@ -2,9 +2,12 @@
 
 extern crate test;
 
-use std::{fs::File, i64, io::Read, usize};
+use std::{collections::HashMap, fs::File, io::Read};
 
+use anyhow::{anyhow, Error};
+use aoc2023_day16b::contraption::{self, Direction, Field, Tile};
 use clap::Parser;
+use iter_tools::Itertools;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -13,161 +16,244 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
+    println!("{}", result);
+
+    Ok(())
 }
 
-fn run(content: &str) -> usize {
-    let field = parse(content);
+fn run(content: &str) -> Result<usize, Error> {
+    let field = contraption::parse(content)?;
+    let graph = BeamGraph::new(&field);
 
+    entries(&field)
+        .map(|entry| graph.energized(entry))
+        .max()
+        .ok_or(anyhow!("field has no entries"))
+}
+
+fn entries(field: &Field) -> impl Iterator<Item = Beam> + '_ {
     (0..field.height)
         .map(|y| ((0, y), Direction::Right))
         .chain((0..field.height).map(|y| ((field.width - 1, y), Direction::Left)))
         .chain((0..field.width).map(|x| ((x, 0), Direction::Down)))
-        .chain((0..field.width).map(|x| ((x, field.height - 1), Direction::Left)))
-        .map(|((x, y), direction)| {
-            let mut light: State = (0..field.height)
-                .map(|_| (0..field.width).map(|_| [false; 4]).collect())
-                .collect();
+        .chain((0..field.width).map(|x| ((x, field.height - 1), Direction::Up)))
+}
 
-            walk(&field, &mut light, (x as i64, y as i64), direction);
+// Position of a beam and the direction it travels in
+type Beam = ((usize, usize), Direction);
 
-            score(&light)
-        })
-        .max()
-        .unwrap()
+// A segment is the straight part of a beam from where it enters a tile up to the next
+// mirror or splitter that redirects it, or the edge of the field
+#[derive(Debug)]
+struct Segment {
+    tiles: Vec<usize>,
+    next: Vec<usize>,
 }
 
-type State = Vec<Vec<[bool; 4]>>;
-
-fn score(light: &State) -> usize {
-    light
-        .into_iter()
-        .map(|row| {
-            row.into_iter()
-                .map(|l| l.into_iter().any(|w| *w))
-                .filter(|row| *row)
-                .count()
-        })
-        .sum()
+// Segments reachable from each other are collapsed into components, for each component
+// the tiles energized by it and everything downstream are stored as a bitset
+struct BeamGraph {
+    starts: HashMap<Beam, usize>,
+    component: Vec<usize>,
+    energized: Vec<Vec<u64>>,
 }
 
-fn walk(field: &Field, state: &mut State, (x, y): (i64, i64), direction: Direction) {
-    if x < 0 || x > field.width as i64 - 1 || y < 0 || y > field.height as i64 - 1 {
-        return;
-    }
+impl BeamGraph {
+    fn new(field: &Field) -> Self {
+        let mut segments = vec![];
+        let mut starts = HashMap::new();
 
-    let xi = x as usize;
-    let yi = y as usize;
+        let mut stack = entries(field).collect_vec();
+        while let Some(beam) = stack.pop() {
+            if starts.contains_key(&beam) {
+                continue;
+            }
 
-    if state[yi][xi][direction as usize] {
-        return;
+            let (tiles, exits) = trace(field, beam);
+            starts.insert(beam, segments.len());
+            segments.push((tiles, exits.clone()));
+            stack.extend(exits);
+        }
+
+        let segments = segments
+            .into_iter()
+            .map(|(tiles, exits)| Segment {
+                tiles,
+                next: exits.iter().map(|beam| starts[beam]).collect(),
+            })
+            .collect_vec();
+
+        let (component, components) = strongly_connected_components(&segments);
+
+        // Components are numbered in reverse topological order, successors come first
+        let words = (field.width * field.height).div_ceil(64);
+        let mut energized: Vec<Vec<u64>> = vec![];
+        for members in components {
+            let mut bits = vec![0; words];
+
+            for segment in &members {
+                for tile in &segments[*segment].tiles {
+                    bits[tile / 64] |= 1 << (tile % 64);
+                }
+
+                for next in &segments[*segment].next {
+                    if component[*next] != energized.len() {
+                        for (word, other) in bits.iter_mut().zip(&energized[component[*next]]) {
+                            *word |= other;
+                        }
+                    }
+                }
+            }
+
+            energized.push(bits);
+        }
+
+        BeamGraph {
+            starts,
+            component,
+            energized,
+        }
     }
 
-    state[yi][xi][direction as usize] = true;
+    fn energized(&self, entry: Beam) -> usize {
+        let component = self.component[self.starts[&entry]];
 
-    let mut recurse = |direction| {
-        let (x, y) = match direction {
-            Direction::Right => (x + 1, y),
-            Direction::Down => (x, y + 1),
-            Direction::Left => (x - 1, y),
-            Direction::Up => (x, y - 1),
-        };
+        self.energized[component]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+}
 
-        walk(field, state, (x, y), direction)
-    };
-
-    match field.tiles[yi][xi] {
-        Tile::Empty => recurse(direction),
-        Tile::MirrorUp => recurse(match direction {
-            Direction::Right => Direction::Up,
-            Direction::Up => Direction::Right,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Down,
-        }),
-        Tile::MirrorDown => recurse(match direction {
-            Direction::Right => Direction::Down,
-            Direction::Up => Direction::Left,
-            Direction::Down => Direction::Right,
-            Direction::Left => Direction::Up,
-        }),
-        Tile::SplitVertical => match direction {
-            Direction::Down | Direction::Up => recurse(direction),
-            Direction::Right | Direction::Left => {
-                recurse(Direction::Up);
-                recurse(Direction::Down);
+// Follows a beam up to the tile that redirects it, returning the energized tiles and
+// the beams leaving that tile which stay inside the field
+fn trace(field: &Field, ((mut x, mut y), direction): Beam) -> (Vec<usize>, Vec<Beam>) {
+    let mut tiles = vec![];
+
+    loop {
+        tiles.push(y * field.width + x);
+
+        let exits = match (&field.tiles[y][x], direction) {
+            (Tile::Empty, _)
+            | (Tile::SplitVertical, Direction::Down | Direction::Up)
+            | (Tile::SplitHorizontal, Direction::Right | Direction::Left) => {
+                match step(field, (x, y), direction) {
+                    Some(next) => {
+                        (x, y) = next;
+                        continue;
+                    }
+                    None => vec![],
+                }
             }
-        },
-        Tile::SplitHorizontal => match direction {
-            Direction::Right | Direction::Left => recurse(direction),
-            Direction::Down | Direction::Up => {
-                recurse(Direction::Left);
-                recurse(Direction::Right)
-            }
-        },
-    };
-}
+            (Tile::MirrorUp, _) => vec![match direction {
+                Direction::Right => Direction::Up,
+                Direction::Up => Direction::Right,
+                Direction::Down => Direction::Left,
+                Direction::Left => Direction::Down,
+            }],
+            (Tile::MirrorDown, _) => vec![match direction {
+                Direction::Right => Direction::Down,
+                Direction::Up => Direction::Left,
+                Direction::Down => Direction::Right,
+                Direction::Left => Direction::Up,
+            }],
+            (Tile::SplitVertical, _) => vec![Direction::Up, Direction::Down],
+            (Tile::SplitHorizontal, _) => vec![Direction::Left, Direction::Right],
+        };
 
-#[derive(Debug)]
-struct Field {
-    tiles: Vec<Vec<Tile>>,
-    height: usize,
-    width: usize,
-}
+        let exits = exits
+            .into_iter()
+            .filter_map(|direction| step(field, (x, y), direction).map(|next| (next, direction)))
+            .collect();
 
-#[derive(Debug)]
-enum Tile {
-    Empty,
-    MirrorUp,
-    MirrorDown,
-    SplitHorizontal,
-    SplitVertical,
+        return (tiles, exits);
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
-    Right,
-    Down,
-    Left,
-    Up,
+fn step(field: &Field, (x, y): (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+    match direction {
+        Direction::Right => (x + 1 < field.width).then(|| (x + 1, y)),
+        Direction::Down => (y + 1 < field.height).then(|| (x, y + 1)),
+        Direction::Left => x.checked_sub(1).map(|x| (x, y)),
+        Direction::Up => y.checked_sub(1).map(|y| (x, y)),
+    }
 }
 
-// Parsing
-
-fn parse(content: &str) -> Field {
-    let tiles = content
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '.' => Tile::Empty,
-                    '/' => Tile::MirrorUp,
-                    '\\' => Tile::MirrorDown,
-                    '-' => Tile::SplitHorizontal,
-                    '|' => Tile::SplitVertical,
-                    _ => unreachable!(),
-                })
-                .collect()
-        })
-        .collect();
-
-    let height = content.lines().count();
-    let width = content.lines().next().unwrap().len();
-
-    Field {
-        tiles,
-        height,
-        width,
+// Iterative Tarjan, components are emitted in reverse topological order
+fn strongly_connected_components(segments: &[Segment]) -> (Vec<usize>, Vec<Vec<usize>>) {
+    let n = segments.len();
+    let mut index = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut component = vec![usize::MAX; n];
+
+    let mut components = vec![];
+    let mut stack = vec![];
+    let mut counter = 0;
+
+    for root in 0..n {
+        if index[root] != usize::MAX {
+            continue;
+        }
+
+        // Segment and the position of the next successor to visit
+        let mut calls = vec![(root, 0)];
+        index[root] = counter;
+        low[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some((node, i)) = calls.pop() {
+            if let Some(&next) = segments[node].next.get(i) {
+                calls.push((node, i + 1));
+
+                if index[next] == usize::MAX {
+                    index[next] = counter;
+                    low[next] = counter;
+                    counter += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    calls.push((next, 0));
+                } else if on_stack[next] {
+                    low[node] = low[node].min(index[next]);
+                }
+
+                continue;
+            }
+
+            if low[node] == index[node] {
+                let mut members = vec![];
+                loop {
+                    let member = stack.pop().unwrap();
+                    on_stack[member] = false;
+                    component[member] = components.len();
+                    members.push(member);
+
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(members);
+            }
+
+            if let Some((parent, _)) = calls.last() {
+                low[*parent] = low[*parent].min(low[node]);
+            }
+        }
     }
+
+    (component, components)
 }
 
 // testing
@@ -184,10 +270,25 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 51)
     }
 
+    #[test]
+    fn test_entries() {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        let field = contraption::parse(&content).unwrap();
+        let graph = BeamGraph::new(&field);
+
+        assert_eq!(graph.energized(((0, 0), Direction::Right)), 46);
+        assert_eq!(graph.energized(((3, 0), Direction::Down)), 51);
+        assert_eq!(graph.energized(((1, 9), Direction::Up)), 45);
+    }
+
     #[test]
     fn test_long() {
         let file = "long_data";
@@ -195,7 +296,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 8185)
     }
 
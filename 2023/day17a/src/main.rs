@@ -4,6 +4,8 @@ extern crate test;
 
 use std::{collections::BinaryHeap, fs::File, io::Read, usize};
 
+use anyhow::Error;
+use aoc2023_day17b::city::{self, Direction};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -13,21 +15,23 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
+    println!("{}", result);
+
+    Ok(())
 }
 
-fn run(content: &str) -> usize {
-    let field = parse(content);
+fn run(content: &str) -> Result<usize, Error> {
+    let field = city::parse(content)?;
 
     let mut min_cost: Vec<usize> = (0..field.height)
         .map(|_| {
@@ -67,7 +71,7 @@ fn run(content: &str) -> usize {
     }) = frontier.pop()
     {
         if x == field.width - 1 && y == field.height - 1 {
-            return cost;
+            return Ok(cost);
         }
 
         for _ in 0..3 {
@@ -109,7 +113,7 @@ fn run(content: &str) -> usize {
         }
     }
 
-    usize::max_value()
+    Ok(usize::max_value())
 }
 
 #[derive(Debug, Eq)]
@@ -137,69 +141,6 @@ impl Ord for ActionItem {
     }
 }
 
-#[derive(Debug)]
-struct Field {
-    tiles: Vec<usize>,
-    height: usize,
-    width: usize,
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Direction {
-    Right,
-    Down,
-    Left,
-    Up,
-}
-
-impl Direction {
-    fn turn_left(self) -> Self {
-        match self {
-            Direction::Right => Direction::Up,
-            Direction::Down => Direction::Right,
-            Direction::Left => Direction::Down,
-            Direction::Up => Direction::Left,
-        }
-    }
-
-    fn turn_right(self) -> Self {
-        match self {
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-            Direction::Up => Direction::Right,
-        }
-    }
-
-    fn apply(self, (x, y): (usize, usize)) -> Option<(usize, usize)> {
-        match self {
-            Direction::Right => Some((x + 1, y)),
-            Direction::Down => Some((x, y + 1)),
-            Direction::Left => x.checked_sub(1).and_then(|x| Some((x, y))),
-            Direction::Up => y.checked_sub(1).and_then(|y| Some((x, y))),
-        }
-    }
-}
-
-// Parsing
-
-fn parse(content: &str) -> Field {
-    let tiles = content
-        .lines()
-        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap() as usize))
-        .flatten()
-        .collect();
-
-    let height = content.lines().count();
-    let width = content.lines().next().unwrap().len();
-
-    Field {
-        tiles,
-        height,
-        width,
-    }
-}
-
 // testing
 #[cfg(test)]
 mod tests {
@@ -214,7 +155,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 102)
     }
 
@@ -225,7 +166,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 1004)
     }
 
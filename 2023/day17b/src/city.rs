@@ -0,0 +1,104 @@
+use anyhow::{anyhow, Error};
+
+#[derive(Debug)]
+pub struct Field {
+    pub tiles: Vec<usize>,
+    pub height: usize,
+    pub width: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Direction {
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+            Direction::Up => Direction::Left,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::Up => Direction::Right,
+        }
+    }
+
+    pub fn apply(self, (x, y): (usize, usize)) -> Option<(usize, usize)> {
+        match self {
+            Direction::Right => Some((x + 1, y)),
+            Direction::Down => Some((x, y + 1)),
+            Direction::Left => x.checked_sub(1).map(|x| (x, y)),
+            Direction::Up => y.checked_sub(1).map(|y| (x, y)),
+        }
+    }
+}
+
+// Parsing
+
+// A non empty rectangle of single digit heat losses, one per city block
+pub fn parse(content: &str) -> Result<Field, Error> {
+    let height = content.lines().count();
+    let width = content.lines().next().map(str::len).unwrap_or(0);
+    if width == 0 {
+        return Err(anyhow!("map should not be empty"));
+    }
+
+    let mut tiles = Vec::with_capacity(width * height);
+    for line in content.lines() {
+        if line.len() != width {
+            return Err(anyhow!("map should be a rectangle: {}", line));
+        }
+
+        for c in line.chars() {
+            let loss = c
+                .to_digit(10)
+                .ok_or(anyhow!("unexpected character in input: {}", c))?;
+            tiles.push(loss as usize);
+        }
+    }
+
+    Ok(Field {
+        tiles,
+        height,
+        width,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let field = parse("241\n321\n").unwrap();
+        assert_eq!((field.width, field.height), (3, 2));
+        assert_eq!(field.tiles, vec![2, 4, 1, 3, 2, 1]);
+
+        assert!(parse("").is_err());
+        assert!(parse("241\n32").is_err());
+        assert!(parse("241\n32x").is_err());
+        assert!(parse("2é\n321").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            if let Ok(field) = parse(&content) {
+                prop_assert_eq!(field.tiles.len(), field.width * field.height);
+            }
+        }
+    }
+}
@@ -2,10 +2,11 @@
 
 extern crate test;
 
-use std::{fs::File, i32, io::Read, usize};
+use std::{fs::File, io::Read};
 
+use anyhow::Error;
+use aoc2023_day18b::lagoon::{self, Instruction};
 use clap::Parser;
-use iter_tools::Itertools;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -14,90 +15,24 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
-}
-
-fn run(content: &str) -> i32 {
-    let instructions = parse(content);
-
-    let mut corners = vec![(0, 0)];
-    calculate_corners(&mut corners, &instructions);
-
-    let mut a = 0;
-
-    for i in 0..corners.len() {
-        let j = (i + 1) % corners.len();
-        a += corners[i].0 * corners[j].1;
-        a -= corners[i].1 * corners[j].0;
-    }
-
-    (a + instructions.into_iter().map(|i| i.length).sum::<i32>()) / 2 + 1
-}
-
-fn calculate_corners(corners: &mut Vec<(i32, i32)>, instructions: &[Instruction]) {
-    if instructions.is_empty() {
-        return;
-    }
-
-    let (mut x, mut y) = corners.last().unwrap();
-    let instruction = instructions.first().unwrap();
-    match instruction.direction {
-        Direction::Right => x += instruction.length,
-        Direction::Down => y += instruction.length,
-        Direction::Left => x -= instruction.length,
-        Direction::Up => y -= instruction.length,
-    }
-    corners.push((x, y));
+    println!("{}", result);
 
-    calculate_corners(corners, &instructions[1..])
+    Ok(())
 }
 
-#[derive(Debug)]
-struct Instruction {
-    direction: Direction,
-    length: i32,
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Direction {
-    Right,
-    Down,
-    Left,
-    Up,
-}
-
-// Parsing
-
-fn parse(content: &str) -> Vec<Instruction> {
-    content.lines().map(Instruction::from).collect()
-}
-
-impl From<&str> for Instruction {
-    fn from(line: &str) -> Self {
-        let mut parts = line.split_ascii_whitespace();
-
-        let direction = match parts.next().unwrap() {
-            "U" => Direction::Up,
-            "R" => Direction::Right,
-            "D" => Direction::Down,
-            "L" => Direction::Left,
-            _ => unreachable!(),
-        };
-
-        let length = parts.next().unwrap().parse::<i32>().unwrap();
-
-        Self { direction, length }
-    }
+fn run(content: &str) -> Result<i64, Error> {
+    let instructions = lagoon::parse(content, Instruction::plain)?;
+    lagoon::area(&instructions)
 }
 
 // testing
@@ -114,7 +49,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 62)
     }
 
@@ -125,7 +60,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 76387)
     }
 
@@ -0,0 +1,169 @@
+use anyhow::{anyhow, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub length: i64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Instruction {
+    // `R 6 (#70c710)` taken as written, six to the right
+    pub fn plain(line: &str) -> Result<Self, Error> {
+        let mut parts = line.split_ascii_whitespace();
+
+        let direction = match parts.next() {
+            Some("U") => Direction::Up,
+            Some("R") => Direction::Right,
+            Some("D") => Direction::Down,
+            Some("L") => Direction::Left,
+            _ => {
+                return Err(anyhow!(
+                    "instruction should start with U, R, D or L: {}",
+                    line
+                ))
+            }
+        };
+
+        let length = parts
+            .next()
+            .and_then(|length| length.parse::<u32>().ok())
+            .ok_or(anyhow!("instruction should have a length: {}", line))?
+            .into();
+
+        Ok(Instruction { direction, length })
+    }
+
+    // `R 6 (#70c710)` read from the colour, five hex digits of length and the direction
+    pub fn hex(line: &str) -> Result<Self, Error> {
+        let colour = line
+            .split_ascii_whitespace()
+            .nth(2)
+            .and_then(|part| part.strip_prefix("(#"))
+            .and_then(|part| part.strip_suffix(')'))
+            .filter(|colour| colour.len() == 6 && colour.is_ascii())
+            .ok_or(anyhow!(
+                "instruction should end in a colour (#rrggbb): {}",
+                line
+            ))?;
+
+        let direction = match &colour[5..] {
+            "3" => Direction::Up,
+            "0" => Direction::Right,
+            "1" => Direction::Down,
+            "2" => Direction::Left,
+            _ => return Err(anyhow!("colour should end in a direction 0 to 3: {}", line)),
+        };
+
+        let length = i64::from_str_radix(&colour[..5], 16)?;
+
+        Ok(Instruction { direction, length })
+    }
+}
+
+pub fn parse(
+    content: &str,
+    read: fn(&str) -> Result<Instruction, Error>,
+) -> Result<Vec<Instruction>, Error> {
+    content.lines().map(read).collect()
+}
+
+// Cubic meters of the trench and everything it encloses, by the shoelace formula plus
+// the half of the trench outside the corner points
+pub fn area(instructions: &[Instruction]) -> Result<i64, Error> {
+    let too_large = || anyhow!("dig plan is too large");
+
+    let mut corners = vec![(0i64, 0i64)];
+    for instruction in instructions {
+        let (x, y) = corners[corners.len() - 1];
+        let length = instruction.length;
+        let corner = match instruction.direction {
+            Direction::Right => (x.checked_add(length), Some(y)),
+            Direction::Down => (Some(x), y.checked_add(length)),
+            Direction::Left => (x.checked_sub(length), Some(y)),
+            Direction::Up => (Some(x), y.checked_sub(length)),
+        };
+        match corner {
+            (Some(x), Some(y)) => corners.push((x, y)),
+            _ => return Err(too_large()),
+        }
+    }
+
+    if corners[corners.len() - 1] != (0, 0) {
+        return Err(anyhow!("dig plan does not return to its start"));
+    }
+
+    let mut twice: i64 = 0;
+    for w in corners.windows(2) {
+        twice = w[0]
+            .0
+            .checked_mul(w[1].1)
+            .zip(w[0].1.checked_mul(w[1].0))
+            .and_then(|(a, b)| a.checked_sub(b))
+            .and_then(|cross| twice.checked_add(cross))
+            .ok_or_else(too_large)?;
+    }
+    let trench = instructions
+        .iter()
+        .try_fold(0i64, |sum, i| sum.checked_add(i.length))
+        .ok_or_else(too_large)?;
+
+    twice
+        .checked_abs()
+        .and_then(|twice| twice.checked_add(trench))
+        .map(|sum| sum / 2 + 1)
+        .ok_or_else(too_large)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_instructions() {
+        let line = "R 6 (#70c710)";
+        assert_eq!(
+            Instruction::plain(line).unwrap(),
+            Instruction {
+                direction: Direction::Right,
+                length: 6
+            }
+        );
+        assert_eq!(
+            Instruction::hex(line).unwrap(),
+            Instruction {
+                direction: Direction::Right,
+                length: 461937
+            }
+        );
+
+        assert!(Instruction::plain("X 6 (#70c710)").is_err());
+        assert!(Instruction::plain("R").is_err());
+        assert!(Instruction::hex("R 6 (#70c714)").is_err());
+        assert!(Instruction::hex("R 6 (#70c7é)").is_err());
+        assert!(Instruction::hex("R 6").is_err());
+    }
+
+    #[test]
+    fn test_invalid_plans() {
+        let plan = parse("R 2 (#000000)\nD 2 (#000000)", Instruction::plain).unwrap();
+        assert!(area(&plan).is_err());
+
+        assert!(Instruction::plain("R -2 (#000000)").is_err());
+
+        // A closed square whose corners multiply beyond i64
+        let square = ["R", "R", "D", "D", "L", "L", "U", "U"]
+            .map(|direction| format!("{} 4000000000 (#000000)", direction))
+            .join("\n");
+        let plan = parse(&square, Instruction::plain).unwrap();
+        assert!(area(&plan).is_err());
+        assert_eq!(area(&plan[..0]).unwrap(), 1);
+    }
+}
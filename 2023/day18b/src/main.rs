@@ -2,10 +2,11 @@
 
 extern crate test;
 
-use std::{fs::File, i32, i64, io::Read, usize};
+use std::{fs::File, io::Read};
 
+use anyhow::Error;
+use aoc2023_day18b::lagoon::{self, Instruction};
 use clap::Parser;
-use iter_tools::Itertools;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -14,98 +15,33 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
-}
-
-fn run(content: &str) -> i64 {
-    let instructions = parse(content);
-
-    let mut corners = vec![(0, 0)];
-    calculate_corners(&mut corners, &instructions);
-
-    let mut a: i64 = 0;
+    println!("{}", result);
 
-    for i in 0..corners.len() {
-        let j = (i + 1) % corners.len();
-        a += corners[i].0 * corners[j].1;
-        a -= corners[i].1 * corners[j].0;
-    }
-
-    (a + instructions.into_iter().map(|i| i.length).sum::<i64>()) / 2 + 1
+    Ok(())
 }
 
-fn calculate_corners(corners: &mut Vec<(i64, i64)>, instructions: &[Instruction]) {
-    if instructions.is_empty() {
-        return;
-    }
-
-    let (mut x, mut y) = corners.last().unwrap();
-    let instruction = instructions.first().unwrap();
-    match instruction.direction {
-        Direction::Right => x += instruction.length,
-        Direction::Down => y += instruction.length,
-        Direction::Left => x -= instruction.length,
-        Direction::Up => y -= instruction.length,
-    }
-    corners.push((x, y));
-
-    calculate_corners(corners, &instructions[1..])
-}
-
-#[derive(Debug)]
-struct Instruction {
-    direction: Direction,
-    length: i64,
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Direction {
-    Right,
-    Down,
-    Left,
-    Up,
-}
-
-// Parsing
-
-fn parse(content: &str) -> Vec<Instruction> {
-    content.lines().map(Instruction::from).collect()
-}
-
-impl From<&str> for Instruction {
-    fn from(line: &str) -> Self {
-        let part = line.split_ascii_whitespace().nth(2).unwrap();
-
-        let direction = match &part[7..8] {
-            "3" => Direction::Up,
-            "0" => Direction::Right,
-            "1" => Direction::Down,
-            "2" => Direction::Left,
-            _ => unreachable!(),
-        };
-
-        let length = i64::from_str_radix(&part[2..7], 16).unwrap();
-
-        Self { direction, length }
-    }
+fn run(content: &str) -> Result<i64, Error> {
+    let instructions = lagoon::parse(content, Instruction::hex)?;
+    lagoon::area(&instructions)
 }
 
 // testing
 #[cfg(test)]
 mod tests {
-    use std::i64;
+    use std::collections::HashSet;
 
     use ::test::Bencher;
+    use proptest::{collection::vec, prelude::*};
 
     use super::*;
 
@@ -116,8 +52,8 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
-        assert_eq!(result, 952408144115 as i64)
+        let result = run(&content).unwrap();
+        assert_eq!(result, 952408144115)
     }
 
     #[test]
@@ -127,10 +63,94 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 250022188522074)
     }
 
+    // Closed plans around a skyline of columns of random width and height, the same plan
+    // written both plainly and in the colours
+    fn skyline() -> impl Strategy<Value = String> {
+        vec((1i64..6, 1i64..6), 1..6).prop_map(|columns| {
+            let mut moves = vec![];
+            let mut height = 0;
+            for (width, column) in &columns {
+                if *column > height {
+                    moves.push(('U', column - height));
+                } else if *column < height {
+                    moves.push(('D', height - column));
+                }
+                height = *column;
+                moves.push(('R', *width));
+            }
+            moves.push(('D', height));
+            moves.push(('L', columns.iter().map(|(width, _)| width).sum()));
+
+            moves
+                .iter()
+                .map(|(direction, length)| {
+                    let digit = "RDLU".find(*direction).unwrap();
+                    format!("{} {} (#{:05x}{})", direction, length, length, digit)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
+
+    // Digs the trench cell by cell and counts everything not reachable from outside
+    fn flood_fill(instructions: &[Instruction]) -> i64 {
+        let mut trench = HashSet::from([(0, 0)]);
+        let (mut x, mut y) = (0i64, 0i64);
+        for instruction in instructions {
+            let (dx, dy) = match instruction.direction {
+                lagoon::Direction::Right => (1, 0),
+                lagoon::Direction::Down => (0, 1),
+                lagoon::Direction::Left => (-1, 0),
+                lagoon::Direction::Up => (0, -1),
+            };
+            for _ in 0..instruction.length {
+                (x, y) = (x + dx, y + dy);
+                trench.insert((x, y));
+            }
+        }
+
+        let min_x = trench.iter().map(|c| c.0).min().unwrap() - 1;
+        let max_x = trench.iter().map(|c| c.0).max().unwrap() + 1;
+        let min_y = trench.iter().map(|c| c.1).min().unwrap() - 1;
+        let max_y = trench.iter().map(|c| c.1).max().unwrap() + 1;
+
+        let mut outside = HashSet::from([(min_x, min_y)]);
+        let mut stack = vec![(min_x, min_y)];
+        while let Some((x, y)) = stack.pop() {
+            for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                let inside_box =
+                    min_x <= next.0 && next.0 <= max_x && min_y <= next.1 && next.1 <= max_y;
+                if inside_box && !trench.contains(&next) && outside.insert(next) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        (max_x - min_x + 1) * (max_y - min_y + 1) - outside.len() as i64
+    }
+
+    proptest! {
+        #[test]
+        fn test_against_flood_fill(content in skyline()) {
+            let plain = lagoon::parse(&content, Instruction::plain).unwrap();
+            let hex = lagoon::parse(&content, Instruction::hex).unwrap();
+            prop_assert_eq!(&plain, &hex);
+
+            prop_assert_eq!(lagoon::area(&plain).unwrap(), flood_fill(&plain));
+            prop_assert_eq!(run(&content).unwrap(), flood_fill(&plain));
+        }
+
+        #[test]
+        fn test_parse_any(line in "\\PC*") {
+            let _ = Instruction::plain(&line);
+            let _ = Instruction::hex(&line);
+        }
+    }
+
     #[bench]
     fn bench(b: &mut Bencher) {
         let file = "long_data";
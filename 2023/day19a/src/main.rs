@@ -2,9 +2,10 @@
 
 extern crate test;
 
-use std::{char, collections::HashMap, fs::File, io::Read, str::FromStr, usize};
+use std::{fs::File, io::Read, usize};
 
 use anyhow::Error;
+use aoc2023_day19b::workflow::{self, Part, Workflow, Workflows};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -14,204 +15,63 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
-}
-
-fn run(content: &str) -> usize {
-    let (workflows, parts) = parse(content);
-
-    parts.into_iter().map(|part| part.process(&workflows)).sum()
-}
-
-impl Part {
-    fn process(self, workflows: &HashMap<String, Workflow>) -> usize {
-        let mut name = "in".to_string();
-        let accepted = loop {
-            let workflow = workflows.get(&name).unwrap();
-            name = workflow.process(&self);
-
-            if name.as_str() == "A" {
-                break true;
-            } else if name.as_str() == "R" {
-                break false;
-            }
-        };
-
-        if accepted {
-            self.values.into_iter().sum()
-        } else {
-            0
-        }
-    }
-}
-
-impl Workflow {
-    fn process(&self, part: &Part) -> String {
-        for rule in &self.rules {
-            if rule.accepts(part) {
-                return rule.destination.clone();
-            }
-        }
-
-        self.final_destination.clone()
-    }
-}
-
-impl Rule {
-    fn accepts(&self, part: &Part) -> bool {
-        match self.condition {
-            Condition::Less => part.values[self.variable as usize] < self.value,
-            Condition::Greater => part.values[self.variable as usize] > self.value,
-        }
-    }
-}
-
-#[derive(Debug)]
-struct Workflow {
-    rules: Vec<Rule>,
-    final_destination: String,
-}
-
-#[derive(Debug)]
-struct Rule {
-    destination: String,
-    variable: Variable,
-    condition: Condition,
-    value: usize,
-}
-
-#[derive(Debug)]
-enum Condition {
-    Less,
-    Greater,
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Variable {
-    X,
-    M,
-    A,
-    S,
-}
-
-#[derive(Debug)]
-struct Part {
-    values: [usize; 4],
-}
+    println!("{}", result);
 
-// Parsing
-
-fn parse(content: &str) -> (HashMap<String, Workflow>, Vec<Part>) {
-    let mut lines = content.lines();
-
-    let workflows = (&mut lines)
-        .take_while(|line| !line.is_empty())
-        .map(|line| {
-            let (name, workflow) = line.split_once("{").unwrap();
-            let name = name.to_string();
-
-            let workflow = workflow.strip_suffix("}").unwrap();
-            Workflow::from_str(workflow).map(|workflow| (name, workflow))
-        })
-        .collect::<Result<_, _>>()
-        .unwrap();
-
-    let parts = lines.map(Part::from_str).collect::<Result<_, _>>().unwrap();
-
-    (workflows, parts)
+    Ok(())
 }
 
-impl FromStr for Workflow {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split(",");
-
-        let rules = parts
-            .clone()
-            .take_while(|p| p.contains(":"))
-            .map(Rule::from_str)
-            .collect::<Result<_, _>>()
-            .unwrap();
+fn run(content: &str) -> Result<usize, Error> {
+    let (workflows, _, parts) = workflow::parse(content)?;
 
-        let final_destination = parts.last().unwrap().to_string();
-
-        Ok(Workflow {
-            rules,
-            final_destination,
-        })
-    }
+    Ok(parts
+        .into_iter()
+        .map(|part| process(part, &workflows))
+        .sum())
 }
 
-impl FromStr for Rule {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut chars = s.chars();
-        let variable = Variable::from(chars.next().unwrap());
-        let condition = Condition::from(chars.next().unwrap());
-
-        let (value, destination) = &s[2..].split_once(":").unwrap();
-        let value = value.parse().unwrap();
-        let destination = destination.to_string();
-
-        Ok(Rule {
-            destination,
-            variable,
-            condition,
-            value,
-        })
-    }
-}
+// Sum of the ratings of the part if the workflows accept it, zero otherwise
+fn process(part: Part, workflows: &Workflows) -> usize {
+    let mut name = "in".to_string();
+    let accepted = loop {
+        let workflow = workflows.get(&name).unwrap();
+        name = route(workflow, &part);
 
-impl FromStr for Part {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.strip_prefix("{").unwrap().strip_suffix("}").unwrap();
-
-        let mut values = [0; 4];
-        for v in s.split(",") {
-            let var = Variable::from(v.chars().next().unwrap());
-            let val = v[2..].parse().unwrap();
-
-            values[var as usize] = val;
+        if name.as_str() == "A" {
+            break true;
+        } else if name.as_str() == "R" {
+            break false;
         }
+    };
 
-        Ok(Part { values })
+    if accepted {
+        part.values.into_iter().sum()
+    } else {
+        0
     }
 }
 
-impl From<char> for Variable {
-    fn from(value: char) -> Self {
-        match value {
-            'x' => Variable::X,
-            'm' => Variable::M,
-            'a' => Variable::A,
-            's' => Variable::S,
-            _ => unreachable!(),
+// Destination of the first rule accepting the part
+fn route(workflow: &Workflow, part: &Part) -> String {
+    for rule in &workflow.rules {
+        if rule
+            .condition
+            .accepts(part.values[rule.variable], rule.value)
+        {
+            return rule.destination.clone();
         }
     }
-}
 
-impl From<char> for Condition {
-    fn from(value: char) -> Self {
-        match value {
-            '<' => Condition::Less,
-            '>' => Condition::Greater,
-            _ => unreachable!(),
-        }
-    }
+    workflow.final_destination.clone()
 }
 
 // testing
@@ -228,10 +88,21 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 19114)
     }
 
+    #[test]
+    fn test_extra() {
+        let file = "extra_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        let result = run(&content).unwrap();
+        assert_eq!(result, 24)
+    }
+
     #[test]
     fn test_long() {
         let file = "long_data";
@@ -239,7 +110,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 330820)
     }
 
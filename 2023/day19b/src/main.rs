@@ -2,10 +2,10 @@
 
 extern crate test;
 
-use core::panic;
-use std::{char, collections::HashMap, fs::File, io::Read, str::FromStr, usize};
+use std::{fs::File, io::Read, usize};
 
-use anyhow::Error;
+use anyhow::{anyhow, Error};
+use aoc2023_day19b::workflow::{self, Rule, Variable, Workflow, Workflows};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -13,28 +13,37 @@ use clap::Parser;
 struct Args {
     #[arg(short, long)]
     file: String,
+    #[arg(long, default_value_t = 1)]
+    min: usize,
+    #[arg(long, default_value_t = 4000)]
+    max: usize,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content, args.min, args.max)?;
 
-    println!("{}", result)
+    println!("{}", result);
+
+    Ok(())
 }
 
-fn run(content: &str) -> usize {
-    let workflows = parse(content);
+fn run(content: &str, min: usize, max: usize) -> Result<usize, Error> {
+    let (workflows, attributes, _) = workflow::parse(content)?;
 
+    let end = max
+        .checked_add(1)
+        .ok_or(anyhow!("maximum value {} is too large", max))?;
     let mut parts = Vec::from([(
         "in".to_string(),
         PartRange {
-            ranges: [(1, 4001); 4],
+            ranges: vec![(min, end); attributes.len()],
         },
     )]);
 
@@ -62,189 +71,89 @@ fn run(content: &str) -> usize {
         }
     }
 
-    accepted.into_iter().map(PartRange::score).sum()
+    accepted
+        .into_iter()
+        .try_fold(0usize, |sum, parts| sum.checked_add(parts.score()?))
+        .ok_or(anyhow!("number of accepted combinations overflows"))
 }
 
 impl PartRange {
-    fn score(self) -> usize {
+    fn score(self) -> Option<usize> {
         self.ranges
             .into_iter()
-            .map(|(min, max)| max.saturating_sub(min))
-            .product()
+            .try_fold(1usize, |product, (min, max)| {
+                product.checked_mul(max.saturating_sub(min))
+            })
     }
 }
 
 fn process_parts(
     parts: Vec<(String, PartRange)>,
-    worklflows: &HashMap<String, Workflow>,
+    worklflows: &Workflows,
 ) -> Vec<(String, PartRange)> {
     parts
         .into_iter()
         .map(|(name, parts)| {
             let workflow = worklflows.get(&name).unwrap();
-            workflow.process(parts).into_iter()
+            process_workflow(workflow, parts).into_iter()
         })
         .flatten()
         .collect()
 }
 
-impl Workflow {
-    fn process(&self, mut parts: PartRange) -> Vec<(String, PartRange)> {
-        let mut res = vec![];
-
-        for rule in &self.rules {
-            if rule.splits(&parts) {
-                let (split, left) = rule.split(parts);
-                res.push((rule.destination.clone(), split));
-                parts = left;
-            }
-        }
-        res.push((self.final_destination.clone(), parts));
-
-        res
-    }
-}
-
-impl Rule {
-    fn splits(&self, parts: &PartRange) -> bool {
-        parts
-            .ranges
-            .iter()
-            .any(|(min, max)| min <= &self.value && &self.value < max)
-    }
-
-    fn split(&self, parts: PartRange) -> (PartRange, PartRange) {
-        let mut split = parts.clone();
-        let mut left = parts;
-
-        match self.condition {
-            Condition::Less => {
-                split.ranges[self.variable as usize].1 = self.value;
-                left.ranges[self.variable as usize].0 = self.value;
-            }
-            Condition::Greater => {
-                split.ranges[self.variable as usize].0 = self.value + 1;
-                left.ranges[self.variable as usize].1 = self.value + 1;
-            }
-        }
+// Splits the parts by the rules of the workflow, pairing each share with where it goes
+fn process_workflow(workflow: &Workflow, parts: PartRange) -> Vec<(String, PartRange)> {
+    let mut res = vec![];
 
-        (split, left)
+    let mut left = vec![parts];
+    for rule in &workflow.rules {
+        left = left
+            .into_iter()
+            .flat_map(|parts| {
+                let (split, left) = split(rule, parts);
+                if let Some(split) = split {
+                    res.push((rule.destination.clone(), split));
+                }
+                left
+            })
+            .collect();
     }
-}
-
-#[derive(Debug, Clone)]
-struct PartRange {
-    ranges: [(usize, usize); 4],
-}
+    res.extend(
+        left.into_iter()
+            .map(|parts| (workflow.final_destination.clone(), parts)),
+    );
 
-#[derive(Debug)]
-struct Workflow {
-    rules: Vec<Rule>,
-    final_destination: String,
+    res
 }
 
-#[derive(Debug)]
-struct Rule {
-    destination: String,
-    variable: Variable,
-    condition: Condition,
-    value: usize,
-}
+// The share of the parts the rule accepts, and what is left of them
+fn split(rule: &Rule, parts: PartRange) -> (Option<PartRange>, Vec<PartRange>) {
+    let (min, max) = parts.ranges[rule.variable];
+    let (lower, upper) = rule.condition.interval(rule.value);
 
-#[derive(Debug)]
-enum Condition {
-    Less,
-    Greater,
-}
-
-#[derive(Debug, Clone, Copy)]
-enum Variable {
-    X,
-    M,
-    A,
-    S,
-}
-
-// Parsing
-
-fn parse(content: &str) -> HashMap<String, Workflow> {
-    content
-        .lines()
-        .take_while(|line| !line.is_empty())
-        .map(|line| {
-            let (name, workflow) = line.split_once("{").unwrap();
-            let name = name.to_string();
-
-            let workflow = workflow.strip_suffix("}").unwrap();
-            Workflow::from_str(workflow).map(|workflow| (name, workflow))
-        })
-        .collect::<Result<_, _>>()
-        .unwrap()
-}
+    let split = (min.max(lower), max.min(upper));
+    let split = (split.0 < split.1).then(|| parts.with_range(rule.variable, split));
 
-impl FromStr for Workflow {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split(",");
-
-        let rules = parts
-            .clone()
-            .take_while(|p| p.contains(":"))
-            .map(Rule::from_str)
-            .collect::<Result<_, _>>()
-            .unwrap();
-
-        let final_destination = parts.last().unwrap().to_string();
-
-        Ok(Workflow {
-            rules,
-            final_destination,
-        })
-    }
-}
-
-impl FromStr for Rule {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut chars = s.chars();
-        let variable = Variable::from(chars.next().unwrap());
-        let condition = Condition::from(chars.next().unwrap());
-
-        let (value, destination) = &s[2..].split_once(":").unwrap();
-        let value = value.parse().unwrap();
-        let destination = destination.to_string();
+    let left = [(min, max.min(lower)), (min.max(upper), max)]
+        .into_iter()
+        .filter(|(min, max)| min < max)
+        .map(|range| parts.with_range(rule.variable, range))
+        .collect();
 
-        Ok(Rule {
-            destination,
-            variable,
-            condition,
-            value,
-        })
-    }
+    (split, left)
 }
 
-impl From<char> for Variable {
-    fn from(value: char) -> Self {
-        match value {
-            'x' => Variable::X,
-            'm' => Variable::M,
-            'a' => Variable::A,
-            's' => Variable::S,
-            _ => unreachable!(),
-        }
+impl PartRange {
+    fn with_range(&self, variable: Variable, range: (usize, usize)) -> PartRange {
+        let mut parts = self.clone();
+        parts.ranges[variable] = range;
+        parts
     }
 }
 
-impl From<char> for Condition {
-    fn from(value: char) -> Self {
-        match value {
-            '<' => Condition::Less,
-            '>' => Condition::Greater,
-            _ => unreachable!(),
-        }
-    }
+#[derive(Debug, Clone)]
+struct PartRange {
+    ranges: Vec<(usize, usize)>,
 }
 
 // testing
@@ -261,10 +170,33 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content, 1, 4000).unwrap();
         assert_eq!(result, 167409079868000)
     }
 
+    #[test]
+    fn test_extra() {
+        let file = "extra_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        let result = run(&content, 1, 10).unwrap();
+        assert_eq!(result, 110)
+    }
+
+    #[test]
+    fn test_overflow() {
+        let content = format!("in{{x>{}:A,R}}\n\n{{x=1}}", usize::MAX);
+        assert!(run(&content, 1, 4000).is_err());
+        assert!(run("in{x>1:A,R}\n\n{x=1}", 1, usize::MAX).is_err());
+
+        // Every combination of four attributes up to 2^16
+        let content = "in{a>0:A,R}\n\n{a=1,b=1,c=1,d=1}";
+        assert_eq!(run(content, 0, 65535).unwrap(), 65535 << 48);
+        assert!(run(content, 0, 65536).is_err());
+    }
+
     #[test]
     fn test_long() {
         let file = "long_data";
@@ -272,7 +204,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content, 1, 4000).unwrap();
         assert_eq!(result, 123972546935551)
     }
 
@@ -283,6 +215,6 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content));
+        b.iter(|| run(&content, 1, 4000));
     }
 }
@@ -0,0 +1,260 @@
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::{anyhow, Error};
+
+// Workflows by name
+pub type Workflows = HashMap<String, Workflow>;
+
+#[derive(Debug)]
+pub struct Workflow {
+    pub rules: Vec<Rule>,
+    pub final_destination: String,
+}
+
+#[derive(Debug)]
+pub struct Rule {
+    pub destination: String,
+    pub variable: Variable,
+    pub condition: Condition,
+    pub value: usize,
+}
+
+#[derive(Debug)]
+pub enum Condition {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+}
+
+impl Condition {
+    pub fn accepts(&self, lhs: usize, rhs: usize) -> bool {
+        match self {
+            Condition::Less => lhs < rhs,
+            Condition::LessEqual => lhs <= rhs,
+            Condition::Greater => lhs > rhs,
+            Condition::GreaterEqual => lhs >= rhs,
+            Condition::Equal => lhs == rhs,
+        }
+    }
+
+    // Half-open interval of values accepted by the condition
+    pub fn interval(&self, value: usize) -> (usize, usize) {
+        match self {
+            Condition::Less => (0, value),
+            Condition::LessEqual => (0, value + 1),
+            Condition::Greater => (value + 1, usize::MAX),
+            Condition::GreaterEqual => (value, usize::MAX),
+            Condition::Equal => (value, value + 1),
+        }
+    }
+}
+
+// Index of an attribute in the order it was first seen in the input
+pub type Variable = usize;
+
+#[derive(Debug, Default)]
+pub struct Attributes {
+    names: HashMap<String, Variable>,
+}
+
+impl Attributes {
+    pub fn intern(&mut self, name: &str) -> Variable {
+        let next = self.names.len();
+        *self.names.entry(name.to_string()).or_insert(next)
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub struct Part {
+    pub values: Vec<usize>,
+}
+
+// Parsing
+
+// Workflows up to the first empty line, then one part per line. Every destination is
+// either accepted `A`, rejected `R` or another workflow, and sorting starts at `in`.
+pub fn parse(content: &str) -> Result<(Workflows, Attributes, Vec<Part>), Error> {
+    let mut attributes = Attributes::default();
+    let mut lines = content.lines();
+
+    let workflows: Workflows = (&mut lines)
+        .take_while(|line| !line.is_empty())
+        .map(|line| {
+            let (name, workflow) = line
+                .split_once('{')
+                .ok_or(anyhow!("workflow without rules: {}", line))?;
+            let name = name.to_string();
+
+            let workflow = workflow
+                .strip_suffix('}')
+                .ok_or(anyhow!("unterminated workflow: {}", line))?;
+            Workflow::parse(workflow, &mut attributes).map(|workflow| (name, workflow))
+        })
+        .collect::<Result<_, _>>()?;
+
+    if !workflows.contains_key("in") {
+        return Err(anyhow!("there is no `in` workflow to start from"));
+    }
+    for workflow in workflows.values() {
+        let destinations = workflow
+            .rules
+            .iter()
+            .map(|rule| &rule.destination)
+            .chain([&workflow.final_destination]);
+        for destination in destinations {
+            if !["A", "R"].contains(&destination.as_str()) && !workflows.contains_key(destination) {
+                return Err(anyhow!("unknown destination: {}", destination));
+            }
+        }
+    }
+
+    let mut parts: Vec<Part> = lines
+        .map(|line| Part::parse(line, &mut attributes))
+        .collect::<Result<_, _>>()?;
+
+    // Attributes a part does not mention default to zero
+    for part in parts.iter_mut() {
+        part.values.resize(attributes.len(), 0);
+    }
+
+    Ok((workflows, attributes, parts))
+}
+
+impl Workflow {
+    fn parse(s: &str, attributes: &mut Attributes) -> Result<Self, Error> {
+        let mut parts = s.split(',');
+
+        let rules = parts
+            .clone()
+            .take_while(|p| p.contains(':'))
+            .map(|rule| Rule::parse(rule, attributes))
+            .collect::<Result<_, _>>()?;
+
+        // Split always yields at least one item
+        let final_destination = parts.next_back().unwrap().to_string();
+
+        Ok(Workflow {
+            rules,
+            final_destination,
+        })
+    }
+}
+
+impl Rule {
+    fn parse(s: &str, attributes: &mut Attributes) -> Result<Self, Error> {
+        let (condition, destination) = s
+            .split_once(':')
+            .ok_or(anyhow!("rule without destination: {}", s))?;
+
+        let operator_start = condition
+            .find(['<', '>', '='])
+            .ok_or(anyhow!("rule without comparison: {}", s))?;
+        let variable = attributes.intern(&condition[..operator_start]);
+
+        let condition = &condition[operator_start..];
+        let operator_len = if condition[1..].starts_with('=') {
+            2
+        } else {
+            1
+        };
+        let (operator, value) = condition.split_at(operator_len);
+
+        let condition = Condition::from_str(operator)?;
+        let value: usize = value.parse()?;
+        // Intervals end one past the value
+        if value == usize::MAX {
+            return Err(anyhow!("comparison value is too large: {}", s));
+        }
+        let destination = destination.to_string();
+
+        Ok(Rule {
+            destination,
+            variable,
+            condition,
+            value,
+        })
+    }
+}
+
+impl Part {
+    fn parse(s: &str, attributes: &mut Attributes) -> Result<Self, Error> {
+        let values_part = s
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .ok_or(anyhow!("part should be enclosed in braces: {}", s))?;
+
+        let mut values = vec![];
+        for v in values_part.split(',') {
+            let (var, val) = v
+                .split_once('=')
+                .ok_or(anyhow!("part value without name: {}", v))?;
+            let var = attributes.intern(var);
+            let val = val.parse()?;
+
+            if values.len() <= var {
+                values.resize(var + 1, 0);
+            }
+            values[var] = val;
+        }
+
+        Ok(Part { values })
+    }
+}
+
+impl FromStr for Condition {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "<" => Ok(Condition::Less),
+            "<=" => Ok(Condition::LessEqual),
+            ">" => Ok(Condition::Greater),
+            ">=" => Ok(Condition::GreaterEqual),
+            "==" => Ok(Condition::Equal),
+            _ => Err(anyhow!("unknown comparison: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        assert!(parse("in{x<5:A,R}\n\n{x=1}").is_ok());
+        assert!(parse("px{x<5:A,R}\n\n{x=1}").is_err());
+        assert!(parse("in{x<5:px,R}\n\n{x=1}").is_err());
+        assert!(parse("in{x<5:A,R\n\n{x=1}").is_err());
+        assert!(parse("in{x<5:A,R}\n\nx=1").is_err());
+        assert!(parse("in{x<5:A,R}\n\n{x}").is_err());
+        assert!(parse("in{x~5:A,R}\n\n{x=1}").is_err());
+    }
+
+    #[test]
+    fn test_attributes() {
+        let (_, attributes, parts) = parse("in{x<5:A,R}\n\n{m=2}\n{x=1,a=3}").unwrap();
+        assert_eq!(attributes.len(), 3);
+        assert_eq!(parts[0].values, vec![0, 2, 0]);
+        assert_eq!(parts[1].values, vec![1, 0, 3]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            let _ = parse(&content);
+        }
+    }
+}
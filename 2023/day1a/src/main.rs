@@ -1,36 +1,57 @@
 use std::{fs::File, io::Read};
 
-fn main() {
-    let mut file = File::open("big_test_file").unwrap();
+use anyhow::Error;
+use aoc2023_day1b::vocabulary::Vocabulary;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[arg(short, long)]
+    file: String,
+
+    /// File of `word = digit` lines to accept besides the numerals
+    #[arg(short, long)]
+    words: Option<String>,
+
+    /// Print the tokens found for the first and last digit of every line
+    #[arg(short, long)]
+    explain: bool,
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
 
+    let mut file = File::open(&args.file)?;
     let mut content: String = "".to_string();
-    file.read_to_string(&mut content).unwrap();
-
-    let mut calibration_numbers = vec![];
-
-    for line in content.lines() {
-        let digits = line.chars().filter(|c| c.is_digit(10)).collect::<Vec<_>>();
-
-        let (c1, c2) = match digits.len() {
-            0 => panic!("{}", line),
-            1 => {
-                let c = digits.first().expect("does exist");
-                (c, c)
-            }
-            _ => (
-                digits.first().expect("does exist"),
-                digits.last().expect("does exist"),
-            ),
-        };
-
-        let val = format!("{}{}", c1, c2)
-            .parse::<u32>()
-            .expect("all should be legal");
-
-        calibration_numbers.push(val)
+    file.read_to_string(&mut content)?;
+
+    let vocabulary = match &args.words {
+        Some(path) => Vocabulary::load(path)?,
+        None => Vocabulary::digits(),
+    };
+
+    if args.explain {
+        for line in content.lines() {
+            let (first, last) = vocabulary.calibration(line)?;
+            println!(
+                "{}: first \"{}\" at {}, last \"{}\" at {} -> {}",
+                line,
+                first.text,
+                first.start,
+                last.text,
+                last.start,
+                first.value * 10 + last.value
+            );
+        }
     }
 
-    let result: u32 = calibration_numbers.iter().sum();
+    let result = content
+        .lines()
+        .map(|l| vocabulary.value(l))
+        .sum::<Result<u32, _>>()?;
+
+    println!("{}", result);
 
-    println!("{}", result)
+    Ok(())
 }
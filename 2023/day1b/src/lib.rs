@@ -0,0 +1,2 @@
+mod trie;
+pub mod vocabulary;
@@ -2,100 +2,63 @@
 
 extern crate test;
 
-mod trie;
-
 use std::{fs::File, io::Read};
 
-use trie::TNode;
-
-const WORDS: [&str; 9] = [
-    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-];
-
-fn main() {
-    let mut file = File::open("big_test_file").unwrap();
-
-    let mut content: String = "".to_string();
-    file.read_to_string(&mut content).unwrap();
-
-    let result = run(&content);
+use anyhow::Error;
+use aoc2023_day1b::vocabulary::Vocabulary;
+use clap::Parser;
 
-    println!("{}", result)
-}
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[arg(short, long)]
+    file: String,
 
-fn create_root() -> TNode {
-    let mut root = TNode::new();
-
-    root.add("1", 1);
-    root.add("2", 2);
-    root.add("3", 3);
-    root.add("4", 4);
-    root.add("5", 5);
-    root.add("6", 6);
-    root.add("7", 7);
-    root.add("8", 8);
-    root.add("9", 9);
-    root.add("one", 1);
-    root.add("two", 2);
-    root.add("three", 3);
-    root.add("four", 4);
-    root.add("five", 5);
-    root.add("six", 6);
-    root.add("seven", 7);
-    root.add("eight", 8);
-    root.add("nine", 9);
-
-    root
-}
+    /// File of `word = digit` lines to use instead of the english words
+    #[arg(short, long)]
+    words: Option<String>,
 
-fn run(content: &str) -> u32 {
-    let root = create_root();
-    content.lines().map(|l| process(l, &root)).sum()
+    /// Print the tokens found for the first and last digit of every line
+    #[arg(short, long)]
+    explain: bool,
 }
 
-fn process(line: &str, root: &TNode) -> u32 {
-    let mut open = vec![];
-    let mut digits = vec![];
-
-    // let collect_terminal = || {
-    //};
-
-    for c in line.chars() {
-        open.retain(|o: &&TNode| match o.value {
-            None => true,
-            Some(value) => {
-                digits.push(value);
-                false
-            }
-        });
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
 
-        open = open.iter().filter_map(|p| p.next.get(&c)).collect();
-
-        if let Some(child) = root.next.get(&c) {
-            open.push(child)
+    let mut file = File::open(&args.file)?;
+    let mut content: String = "".to_string();
+    file.read_to_string(&mut content)?;
+
+    let vocabulary = match &args.words {
+        Some(path) => Vocabulary::load(path)?,
+        None => Vocabulary::english(),
+    };
+
+    if args.explain {
+        for line in content.lines() {
+            let (first, last) = vocabulary.calibration(line)?;
+            println!(
+                "{}: first \"{}\" at {}, last \"{}\" at {} -> {}",
+                line,
+                first.text,
+                first.start,
+                last.text,
+                last.start,
+                first.value * 10 + last.value
+            );
         }
     }
-    open.retain(|o: &&TNode| match o.value {
-        None => true,
-        Some(value) => {
-            digits.push(value);
-            false
-        }
-    });
 
-    match digits.len() {
-        0 => panic!("malformed line: {}", line),
-        1 => {
-            let first = digits.first().expect("has at least one digit");
-            first * 10 + first
-        }
-        _ => {
-            let first = digits.first().expect("has at least one digit");
-            let last = digits.last().expect("has at least one digit");
+    let result = run(&content, &vocabulary)?;
 
-            first * 10 + last
-        }
-    }
+    println!("{}", result);
+
+    Ok(())
+}
+
+fn run(content: &str, vocabulary: &Vocabulary) -> Result<u32, Error> {
+    content.lines().map(|l| vocabulary.value(l)).sum()
 }
 
 #[cfg(test)]
@@ -110,7 +73,7 @@ mod tests {
         let mut content: String = "".to_string();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content, &Vocabulary::english()).unwrap();
 
         assert_eq!(result, 53539);
     }
@@ -121,6 +84,7 @@ mod tests {
 
         let mut content: String = "".to_string();
         file.read_to_string(&mut content).unwrap();
-        b.iter(|| run(&content));
+        let vocabulary = Vocabulary::english();
+        b.iter(|| run(&content, &vocabulary));
     }
 }
@@ -0,0 +1,182 @@
+use std::{cmp::Reverse, fs, str::FromStr};
+
+use anyhow::{anyhow, Error};
+
+use crate::trie::TNode;
+
+const ENGLISH: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+// Words that spell a digit, looked up through a trie
+#[derive(Debug)]
+pub struct Vocabulary {
+    root: TNode,
+}
+
+// A word of the vocabulary found in a line, offsets count characters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub text: String,
+    pub value: u32,
+    pub start: usize,
+}
+
+impl Vocabulary {
+    // Only the numerals 0 to 9
+    pub fn digits() -> Self {
+        let mut root = TNode::new();
+        for value in 0..10 {
+            root.add(&value.to_string(), value);
+        }
+
+        Vocabulary { root }
+    }
+
+    pub fn english() -> Self {
+        let mut vocabulary = Vocabulary::digits();
+        for (word, value) in ENGLISH.iter().zip(1..) {
+            vocabulary.add(word, value);
+        }
+
+        vocabulary
+    }
+
+    // The numerals plus the words of a file of `word = value` lines
+    pub fn load(path: &str) -> Result<Self, Error> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    pub fn add(&mut self, word: &str, value: u32) {
+        self.root.add(word, value)
+    }
+
+    // Every occurrence of every word, ordered by offset. Occurrences may overlap, so
+    // `eightwo` holds both `eight` at 0 and `two` at 4.
+    pub fn tokens(&self, line: &str) -> Vec<Token> {
+        let chars: Vec<char> = line.chars().collect();
+        let mut tokens = vec![];
+
+        for start in 0..chars.len() {
+            let mut node = &self.root;
+            for (len, c) in chars[start..].iter().enumerate() {
+                match node.next.get(c) {
+                    Some(next) => node = next,
+                    None => break,
+                }
+
+                if let Some(value) = node.value {
+                    tokens.push(Token {
+                        text: chars[start..=start + len].iter().collect(),
+                        value,
+                        start,
+                    });
+                }
+            }
+        }
+
+        tokens
+    }
+
+    // The first and the last digit of a line. Where several words start at the same
+    // offset the longest one wins.
+    pub fn calibration(&self, line: &str) -> Result<(Token, Token), Error> {
+        let tokens = self.tokens(line);
+
+        let first = tokens
+            .iter()
+            .min_by_key(|token| (token.start, Reverse(token.text.len())))
+            .ok_or(anyhow!("no digit in line: {}", line))?;
+        let last = tokens
+            .iter()
+            .max_by_key(|token| (token.start, token.text.len()))
+            .ok_or(anyhow!("no digit in line: {}", line))?;
+
+        Ok((first.clone(), last.clone()))
+    }
+
+    pub fn value(&self, line: &str) -> Result<u32, Error> {
+        let (first, last) = self.calibration(line)?;
+        Ok(first.value * 10 + last.value)
+    }
+}
+
+impl FromStr for Vocabulary {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut vocabulary = Vocabulary::digits();
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (word, value) = line
+                .split_once('=')
+                .ok_or(anyhow!("word should have the form word = value: {}", line))?;
+            let value: u32 = value.trim().parse()?;
+            if value > 9 {
+                return Err(anyhow!("{} does not spell a single digit", line));
+            }
+
+            vocabulary.add(word.trim(), value);
+        }
+
+        Ok(vocabulary)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_overlaps() {
+        let vocabulary = Vocabulary::english();
+
+        let texts: Vec<String> = vocabulary
+            .tokens("eightwothree")
+            .into_iter()
+            .map(|token| format!("{}@{}", token.text, token.start))
+            .collect();
+        assert_eq!(texts, vec!["eight@0", "two@4", "three@7"]);
+
+        assert_eq!(vocabulary.value("eightwo").unwrap(), 82);
+        assert_eq!(vocabulary.value("treb7uchet").unwrap(), 77);
+        assert_eq!(vocabulary.value("zoneight234").unwrap(), 14);
+        assert!(vocabulary.value("nothing").is_err());
+
+        assert_eq!(Vocabulary::digits().value("two1nine").unwrap(), 11);
+    }
+
+    #[test]
+    fn test_custom() {
+        let vocabulary: Vocabulary = "
+            # german
+            eins = 1
+            zwei = 2
+            drei = 3
+            sieben = 7
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(vocabulary.value("xzweinsx").unwrap(), 21);
+        assert_eq!(vocabulary.value("siebendrei4").unwrap(), 74);
+        assert!("zehn = 10".parse::<Vocabulary>().is_err());
+
+        let prefixes: Vocabulary = "a = 1\nab = 2".parse().unwrap();
+        assert_eq!(prefixes.value("xabx").unwrap(), 22);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            let _ = content.parse::<Vocabulary>();
+            let _ = Vocabulary::english().value(&content);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::{anyhow, Error};
+use iter_tools::Itertools;
+
+#[derive(Debug)]
+pub struct Circuit {
+    gates: HashMap<String, Gate>,
+    sources: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug)]
+struct Gate {
+    name: String,
+    destinations: Vec<String>,
+    gate_type: GateType,
+}
+
+#[derive(Debug)]
+enum GateType {
+    ID,
+    FlipFlop { state: bool },
+    Conjunction { state: HashMap<String, bool> },
+}
+
+impl Circuit {
+    // Pushes the button once, every signal sent is passed to `observe` as
+    // (source, destination, signal) in the order it is delivered
+    pub fn press(&mut self, mut observe: impl FnMut(&str, &str, bool)) {
+        let mut signals = vec![("".to_string(), "broadcaster".to_string(), false)];
+
+        while !signals.is_empty() {
+            let mut outputs = vec![];
+
+            for (source, destination, signal) in signals {
+                observe(&source, &destination, signal);
+
+                if let Some(gate) = self.gates.get_mut(&destination) {
+                    let mut gate_out = gate.process(source, signal);
+                    outputs.append(&mut gate_out);
+                }
+            }
+
+            signals = outputs;
+        }
+    }
+
+    // Names of the gates sending to `name`
+    pub fn sources(&self, name: &str) -> Option<&Vec<String>> {
+        self.sources.get(name)
+    }
+}
+
+impl Gate {
+    fn process(&mut self, source: String, signal: bool) -> Vec<(String, String, bool)> {
+        let mut out = vec![];
+
+        let mut send = |signal| {
+            for destination in &self.destinations {
+                out.push((self.name.to_string(), destination.to_string(), signal));
+            }
+        };
+
+        match &mut self.gate_type {
+            GateType::ID => send(signal),
+            GateType::FlipFlop { state } => {
+                if !signal {
+                    *state = !*state;
+                    send(*state)
+                }
+            }
+            GateType::Conjunction { state } => {
+                state.entry(source).and_modify(|v| *v = signal);
+                let signal = !state.values().all(|v| *v);
+                send(signal)
+            }
+        }
+
+        out
+    }
+}
+
+// Parsing
+
+pub fn parse(content: &str) -> Result<Circuit, Error> {
+    let builder: CircuitBuilder = content.parse()?;
+    Ok(builder.build())
+}
+
+pub struct CircuitBuilder {
+    sources: HashMap<String, Vec<String>>,
+    gates: HashMap<String, Gate>,
+}
+
+impl FromStr for CircuitBuilder {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sources = HashMap::new();
+        let mut gates = HashMap::new();
+
+        for line in s.lines() {
+            let (gate, destinations) = line.split_once(" -> ").ok_or(anyhow!(
+                "gate should have the form name -> destinations: {}",
+                line
+            ))?;
+
+            let destinations = destinations
+                .split(", ")
+                .map(|d| d.to_string())
+                .collect_vec();
+
+            let gate = if let Some(name) = gate.strip_prefix('%') {
+                Gate {
+                    name: name.to_string(),
+                    destinations: destinations.clone(),
+                    gate_type: GateType::FlipFlop { state: false },
+                }
+            } else if let Some(name) = gate.strip_prefix('&') {
+                Gate {
+                    name: name.to_string(),
+                    destinations: destinations.clone(),
+                    gate_type: GateType::Conjunction {
+                        state: HashMap::new(),
+                    },
+                }
+            } else {
+                Gate {
+                    name: gate.to_string(),
+                    destinations: destinations.clone(),
+                    gate_type: GateType::ID,
+                }
+            };
+
+            if gate.name.is_empty() || gate.destinations.iter().any(|d| d.is_empty()) {
+                return Err(anyhow!("gate names should not be empty: {}", line));
+            }
+            if gates.contains_key(&gate.name) {
+                return Err(anyhow!("gate {} is defined twice", gate.name));
+            }
+
+            for destination in destinations {
+                sources
+                    .entry(destination)
+                    .or_insert_with(Vec::new)
+                    .push(gate.name.to_string());
+            }
+
+            gates.insert(gate.name.to_string(), gate);
+        }
+
+        Ok(Self { sources, gates })
+    }
+}
+
+impl CircuitBuilder {
+    pub fn build(mut self) -> Circuit {
+        for gate in self.gates.values_mut() {
+            if let GateType::Conjunction { state } = &mut gate.gate_type {
+                // A conjunction nothing sends to keeps an empty memory
+                if let Some(sources) = self.sources.get(&gate.name) {
+                    for s in sources {
+                        state.insert(s.to_string(), false);
+                    }
+                }
+            }
+        }
+
+        Circuit {
+            gates: self.gates,
+            sources: self.sources,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        assert!(parse("broadcaster -> a\n%a").is_err());
+        assert!(parse("broadcaster -> a\n% -> b").is_err());
+        assert!(parse("broadcaster -> a, ").is_err());
+        assert!(parse("%a -> b\n&a -> b").is_err());
+
+        // A lone conjunction only ever sends high
+        let mut circuit = parse("broadcaster -> b\n&a -> b").unwrap();
+        circuit.press(|_, _, _| ());
+        assert_eq!(circuit.sources("b").unwrap(), &vec!["broadcaster", "a"]);
+    }
+}
@@ -2,8 +2,10 @@
 
 extern crate test;
 
-use std::{collections::HashSet, fs::File, io::Read, usize};
+use std::{collections::VecDeque, fs::File, io::Read};
 
+use anyhow::{anyhow, Error};
+use aoc2023_day21b::garden::{self, Field, Tile};
 use clap::Parser;
 use iter_tools::Itertools;
 
@@ -12,79 +14,145 @@ use iter_tools::Itertools;
 struct Args {
     #[arg(short, long)]
     file: String,
+    #[arg(short, long, value_delimiter = ',', default_values_t = [64])]
+    steps: Vec<usize>,
+    /// Additional start positions as x:y
+    #[arg(long, value_parser = parse_position)]
+    start: Vec<Position>,
     #[arg(short, long)]
-    steps: usize,
+    render: bool,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content, args.steps);
+    let (field, mut starts) = garden::parse(&content)?;
+    for (x, y) in args.start {
+        match field.get(y).and_then(|row| row.get(x)) {
+            Some(Tile::Empty) => starts.push((x, y)),
+            Some(Tile::Stone) => return Err(anyhow!("start {}:{} is a rock", x, y)),
+            None => return Err(anyhow!("start {}:{} is outside the field", x, y)),
+        }
+    }
 
-    println!("{}", result)
-}
+    let distances = DistanceMap::new(&field, &starts);
 
-fn run(content: &str, steps: usize) -> usize {
-    let (field, start) = parse(content);
+    for steps in args.steps {
+        if args.render {
+            println!("{}\n", distances.render(&field, &starts, steps));
+        }
+
+        println!("{}", distances.reachable(steps))
+    }
 
-    let mut end = HashSet::new();
-    let mut seen = (0..=steps).map(|_| HashSet::new()).collect_vec();
-    walk(&field, &mut end, &mut seen, start, steps);
-    end.len()
+    Ok(())
 }
 
-fn walk(
-    field: &Field,
-    end: &mut HashSet<Position>,
-    seen: &mut Vec<HashSet<Position>>,
-    (x, y): Position,
-    steps: usize,
-) {
-    if y >= field.len()
-        || x >= field[0].len()
-        || field[y][x] == Tile::Stone
-        || seen[steps].contains(&(x, y))
-    {
-        return;
+// Shortest even and odd walk from any start to every plot. A plot can be reached in
+// exactly n steps iff the shortest walk of the same parity as n is at most n long,
+// as the walk can always be padded by stepping back and forth.
+struct DistanceMap {
+    distances: Vec<Vec<[Option<usize>; 2]>>,
+    reachable: [Vec<usize>; 2],
+}
+
+impl DistanceMap {
+    fn new(field: &Field, starts: &[Position]) -> Self {
+        let mut distances = vec![vec![[None; 2]; field[0].len()]; field.len()];
+
+        let mut queue = VecDeque::new();
+        for (x, y) in starts {
+            distances[*y][*x][0] = Some(0);
+            queue.push_back(((*x, *y), 0));
+        }
+
+        while let Some((position, distance)) = queue.pop_front() {
+            let parity = (distance + 1) % 2;
+
+            for (x, y) in DIRECTIONS
+                .into_iter()
+                .filter_map(|direction| direction.apply(position))
+            {
+                if y >= field.len() || x >= field[0].len() || field[y][x] == Tile::Stone {
+                    continue;
+                }
+
+                if distances[y][x][parity].is_none() {
+                    distances[y][x][parity] = Some(distance + 1);
+                    queue.push_back(((x, y), distance + 1));
+                }
+            }
+        }
+
+        // Prefix counts of plots by shortest walk length for each parity
+        let reachable = [0, 1].map(|parity| {
+            let counts = distances
+                .iter()
+                .flatten()
+                .filter_map(|distance| distance[parity])
+                .counts();
+
+            let max = counts.keys().copied().max().unwrap_or(0);
+            (0..=max)
+                .scan(0, |total, distance| {
+                    *total += counts.get(&distance).unwrap_or(&0);
+                    Some(*total)
+                })
+                .collect_vec()
+        });
+
+        DistanceMap {
+            distances,
+            reachable,
+        }
     }
 
-    if steps == 0 {
-        end.insert((x, y));
-        return;
+    fn reachable(&self, steps: usize) -> usize {
+        let reachable = &self.reachable[steps % 2];
+
+        reachable
+            .get(steps)
+            .or(reachable.last())
+            .copied()
+            .unwrap_or(0)
     }
 
-    seen[steps].insert((x, y));
-    let steps = steps - 1;
-
-    [
-        Directions::Up,
-        Directions::Right,
-        Directions::Down,
-        Directions::Left,
-    ]
-    .into_iter()
-    .for_each(|direction| {
-        direction
-            .apply((x, y))
-            .map(|position| walk(field, end, seen, position, steps));
-    })
-}
+    fn is_reachable(&self, (x, y): Position, steps: usize) -> bool {
+        self.distances[y][x][steps % 2].is_some_and(|distance| distance <= steps)
+    }
 
-type Field = Vec<Vec<Tile>>;
-#[derive(Debug, PartialEq)]
-enum Tile {
-    Empty,
-    Stone,
+    fn render(&self, field: &Field, starts: &[Position], steps: usize) -> String {
+        field
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, tile)| {
+                        if self.is_reachable((x, y), steps) {
+                            'O'
+                        } else if starts.contains(&(x, y)) {
+                            'S'
+                        } else if *tile == Tile::Stone {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .join("\n")
+    }
 }
 
 type Position = (usize, usize);
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum Directions {
     Up,
     Right,
@@ -92,6 +160,13 @@ enum Directions {
     Left,
 }
 
+const DIRECTIONS: [Directions; 4] = [
+    Directions::Up,
+    Directions::Right,
+    Directions::Down,
+    Directions::Left,
+];
+
 impl Directions {
     fn apply(self, (x, y): Position) -> Option<Position> {
         match self {
@@ -105,36 +180,12 @@ impl Directions {
 
 // Parsing
 
-fn parse(content: &str) -> (Field, Position) {
-    let field = content
-        .lines()
-        .map(|line| line.chars().map(Tile::from).collect_vec())
-        .collect_vec();
-
-    let start = content
-        .lines()
-        .enumerate()
-        .map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter(|(_, c)| *c == 'S')
-                .map(move |(x, _)| (x, y))
-        })
-        .flatten()
-        .next()
-        .unwrap();
-
-    (field, start)
-}
+fn parse_position(s: &str) -> Result<Position, Error> {
+    let (x, y) = s
+        .split_once(':')
+        .ok_or(anyhow!("position should have the form x:y: {}", s))?;
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
-        match value {
-            '#' => Tile::Stone,
-            '.' | 'S' => Tile::Empty,
-            _ => unreachable!(),
-        }
-    }
+    Ok((x.parse()?, y.parse()?))
 }
 
 // testing
@@ -144,6 +195,12 @@ mod tests {
 
     use super::*;
 
+    fn run(content: &str, steps: usize) -> usize {
+        let (field, starts) = garden::parse(content).unwrap();
+
+        DistanceMap::new(&field, &starts).reachable(steps)
+    }
+
     #[test]
     fn test_short() {
         let file = "short_data";
@@ -155,6 +212,43 @@ mod tests {
         assert_eq!(result, 16)
     }
 
+    #[test]
+    fn test_render() {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        let (field, starts) = garden::parse(&content).unwrap();
+        let distances = DistanceMap::new(&field, &starts);
+
+        assert_eq!(distances.reachable(1), 2);
+        assert_eq!(distances.reachable(2), 4);
+        assert_eq!(distances.reachable(3), 6);
+        assert_eq!(
+            distances.render(&field, &starts, 6),
+            "...........
+.....###.#.
+.###.##.O#.
+.O#O#O.O#..
+O.O.#.#.O..
+.##O.O####.
+.##.O#O..#.
+.O.O.O.##..
+.##.#.####.
+.##O.##.##.
+..........."
+        );
+    }
+
+    #[test]
+    fn test_positions() {
+        assert_eq!(parse_position("3:5").unwrap(), (3, 5));
+        assert!(parse_position("3,5").is_err());
+        assert!(parse_position("-1:5").is_err());
+        assert!(parse_position("3:").is_err());
+    }
+
     #[test]
     fn test_long() {
         let file = "long_data";
@@ -0,0 +1,75 @@
+use anyhow::{anyhow, Error};
+
+pub type Field = Vec<Vec<Tile>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tile {
+    Empty,
+    Stone,
+}
+
+// Parsing
+
+// A non empty rectangle of garden plots and rocks, along with the column and row of
+// every start `S`, which is a garden plot as well
+pub fn parse(content: &str) -> Result<(Field, Vec<(usize, usize)>), Error> {
+    let field: Field = content
+        .lines()
+        .map(|line| line.chars().map(Tile::try_from).collect())
+        .collect::<Result<_, _>>()?;
+
+    let width = field.first().map(Vec::len).unwrap_or(0);
+    if width == 0 || field.iter().any(|row| row.len() != width) {
+        return Err(anyhow!("garden should be a non empty rectangle"));
+    }
+
+    let starts = content
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == 'S')
+                .map(move |(x, _)| (x, y))
+        })
+        .collect();
+
+    Ok((field, starts))
+}
+
+impl TryFrom<char> for Tile {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '#' => Ok(Tile::Stone),
+            '.' | 'S' => Ok(Tile::Empty),
+            _ => Err(anyhow!("unexpected character in input: {}", value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let (field, starts) = parse("S.#\n#.S\n").unwrap();
+        assert_eq!(field[1], vec![Tile::Stone, Tile::Empty, Tile::Empty]);
+        assert_eq!(starts, vec![(0, 0), (2, 1)]);
+
+        assert!(parse("").is_err());
+        assert!(parse("S.#\n#.").is_err());
+        assert!(parse("S.#\n#.x").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            let _ = parse(&content);
+        }
+    }
+}
@@ -1,13 +1,10 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs::File,
-    i32,
-    io::Read,
-    usize,
-};
+use std::{collections::VecDeque, fs::File, io::Read};
 
+use anyhow::{anyhow, Error};
+use aoc2023_day21b::garden::{self, Field, Tile};
 use clap::Parser;
 use iter_tools::Itertools;
+use num::Integer;
 use strum::{EnumIter, IntoEnumIterator};
 
 #[derive(Parser, Debug)]
@@ -15,167 +12,166 @@ use strum::{EnumIter, IntoEnumIterator};
 struct Args {
     #[arg(short, long)]
     file: String,
-    #[arg(short, long)]
+    #[arg(short, long, default_value_t = 26501365)]
     steps: usize,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
+
+    let result = run(&content, args.steps)?;
 
-    let result = run(&content, args.steps);
+    println!("{}", result);
 
-    println!("{}", result)
+    Ok(())
 }
 
-fn run(content: &str, steps: usize) -> usize {
-    let (layout, start) = parse(content);
+fn run(content: &str, steps: usize) -> Result<usize, Error> {
+    let (layout, start) = parse(content)?;
 
+    extrapolate(&layout, start, steps)
+}
+
+// The reachable count is sampled at steps congruent to `steps` modulo a multiple of the
+// tile size. Once the walk has left the first few tiles the samples grow quadratically,
+// so a quadratic fitted on three samples is checked against two more before using it.
+fn extrapolate(layout: &Field, start: Position, steps: usize) -> Result<usize, Error> {
     let height = layout.len();
     let width = layout[0].len();
+    let tile = width.lcm(&height);
 
-    let mut cache = HashMap::new();
-    let mut fields = HashMap::from([((0, 0), Vec::from([(start)]))]);
+    let mut distances = Distances::new(layout, start, 0);
 
-    for _ in 0..steps {
-        let mut new_fields = HashMap::new();
-        let mut emmissions = HashMap::new();
+    for warmup in 0..MAX_WARMUP {
+        for period in [tile, 2 * tile] {
+            let remainder = steps % period;
+            let samples = (0..5)
+                .map(|i| remainder + (warmup + i) * period)
+                .collect_vec();
 
-        for (position, field) in fields {
-            let (field, emmission) = step(&mut cache, field, &layout, height, width);
-            new_fields.insert(position, field);
-            emmissions.insert(position, emmission);
-        }
+            let max_steps = samples[samples.len() - 1];
+            if steps <= max_steps {
+                distances = Distances::new(layout, start, steps);
+                return Ok(distances.reachable(steps));
+            }
 
-        apply_emmissions(&mut new_fields, emmissions, height, width);
+            if distances.radius < max_steps {
+                distances = Distances::new(layout, start, max_steps.max(2 * distances.radius));
+            }
 
-        fields = new_fields;
-    }
+            let values = samples
+                .iter()
+                .map(|steps| distances.reachable(*steps) as i128)
+                .collect_vec();
 
-    fields.into_iter().map(|(_, field)| field.len()).sum()
-}
+            let quadratic = Quadratic::fit(values[0], values[1], values[2]);
+            if quadratic.at(3) != values[3] || quadratic.at(4) != values[4] {
+                continue;
+            }
 
-fn step(
-    cache: &mut Cache,
-    field: FieldState,
-    layout: &Field,
-    height: usize,
-    width: usize,
-) -> (FieldState, FieldEmissions) {
-    let entry = cache.get(&field);
-    match entry {
-        Some(result) => {
-            print!(".");
-            result.clone()
-        }
-        None => {
-            let result = step_no_cache(&field, layout, height, width);
-            cache.insert(field, result.clone());
-            result
+            let result = quadratic.at(((steps - samples[0]) / period) as i128);
+            return usize::try_from(result).map_err(Error::from);
         }
     }
+
+    Err(anyhow!(
+        "no quadratic growth found after {} periods",
+        MAX_WARMUP
+    ))
 }
 
-fn step_no_cache(
-    field: &FieldState,
-    layout: &Field,
-    height: usize,
-    width: usize,
-) -> (FieldState, FieldEmissions) {
-    let positions: HashSet<Position> = field
-        .into_iter()
-        .map(|position| Direction::iter().map(move |direction| direction.apply(*position)))
-        .flatten()
-        .filter(|(x, y)| {
-            *y < 0
-                || *y >= height as i32
-                || *x < 0
-                || *x >= width as i32
-                || layout[*y as usize][*x as usize] != Tile::Stone
-        })
-        .collect();
-
-    let mut emissions: FieldEmissions = Direction::iter()
-        .map(|_| HashSet::new())
-        .collect_vec()
-        .try_into()
-        .unwrap();
-
-    let field = positions
-        .into_iter()
-        .filter_map(|(x, y)| {
-            if y < 0 {
-                emissions[Direction::Up as usize].insert(x);
-                None
-            } else if y >= height as i32 {
-                emissions[Direction::Down as usize].insert(x);
-                None
-            } else if x < 0 {
-                emissions[Direction::Left as usize].insert(y);
-                None
-            } else if x >= width as i32 {
-                emissions[Direction::Right as usize].insert(y);
-                None
-            } else {
-                Some((x, y))
-            }
-        })
-        .sorted()
-        .collect();
+const MAX_WARMUP: usize = 8;
 
-    (field, emissions)
+// Newton form of the quadratic through (0, f0), (1, f1) and (2, f2)
+struct Quadratic {
+    f0: i128,
+    d1: i128,
+    d2: i128,
 }
 
-fn apply_emmissions(
-    fields: &mut Fields,
-    emmissions: HashMap<Position, FieldEmissions>,
-    height: usize,
-    width: usize,
-) {
-    for (position, emmissions) in emmissions {
-        for direction in Direction::iter() {
-            let positions = emmissions[direction as usize]
-                .iter()
-                .map(|c| match direction {
-                    Direction::Up => (*c, height as i32 - 1),
-                    Direction::Right => (0, *c),
-                    Direction::Down => (*c, 0),
-                    Direction::Left => (width as i32 - 1, *c),
-                });
-
-            fields
-                .entry(direction.apply(position))
-                .and_modify(|field| {
-                    for position in positions.clone() {
-                        if !field.contains(&position) {
-                            field.push(position);
-                        }
-                    }
-                    field.sort();
-                })
-                .or_insert_with(|| positions.sorted().collect_vec());
+impl Quadratic {
+    fn fit(f0: i128, f1: i128, f2: i128) -> Self {
+        Quadratic {
+            f0,
+            d1: f1 - f0,
+            d2: f2 - 2 * f1 + f0,
         }
     }
+
+    fn at(&self, k: i128) -> i128 {
+        self.f0 + k * self.d1 + k * (k - 1) / 2 * self.d2
+    }
+}
+
+// Number of garden plots at every distance from the start on the infinitely tiled map,
+// computed by a single BFS over all tiles within `radius` steps
+struct Distances {
+    radius: usize,
+    counts: Vec<usize>,
 }
 
-type Cache = HashMap<FieldState, (FieldState, FieldEmissions)>;
-type Fields = HashMap<Position, FieldState>;
+impl Distances {
+    fn new(layout: &Field, start: Position, radius: usize) -> Self {
+        let height = layout.len() as i64;
+        let width = layout[0].len() as i64;
+        let r = radius as i64;
 
-type FieldState = Vec<Position>;
-type FieldEmissions = [HashSet<i32>; 4];
+        let size = 2 * r + 1;
+        let index = |(x, y): Position| -> Option<usize> {
+            let (dx, dy) = (x - start.0 + r, y - start.1 + r);
+            (0 <= dx && dx < size && 0 <= dy && dy < size).then(|| (dy * size + dx) as usize)
+        };
 
-type Field = Vec<Vec<Tile>>;
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum Tile {
-    Empty,
-    Stone,
+        let mut seen = vec![false; (size * size) as usize];
+        let mut counts = vec![0; radius + 1];
+
+        let mut queue = VecDeque::from([(start, 0)]);
+        seen[index(start).unwrap()] = true;
+
+        while let Some((position, distance)) = queue.pop_front() {
+            counts[distance] += 1;
+
+            if distance == radius {
+                continue;
+            }
+
+            for next in Direction::iter().map(|direction| direction.apply(position)) {
+                let (x, y) = next;
+                if layout[y.rem_euclid(height) as usize][x.rem_euclid(width) as usize]
+                    == Tile::Stone
+                {
+                    continue;
+                }
+
+                let i = index(next).unwrap();
+                if !seen[i] {
+                    seen[i] = true;
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        Distances { radius, counts }
+    }
+
+    // Plots reachable in exactly `steps` steps are those with a distance of the same parity
+    fn reachable(&self, steps: usize) -> usize {
+        assert!(steps <= self.radius);
+
+        self.counts[..=steps]
+            .iter()
+            .skip(steps % 2)
+            .step_by(2)
+            .sum()
+    }
 }
 
-type Position = (i32, i32);
+type Position = (i64, i64);
 
 #[derive(Debug, EnumIter, Clone, Copy)]
 enum Direction {
@@ -198,35 +194,13 @@ impl Direction {
 
 // Parsing
 
-fn parse(content: &str) -> (Field, Position) {
-    let field = content
-        .lines()
-        .map(|line| line.chars().map(Tile::from).collect_vec())
-        .collect_vec();
-
-    let start = content
-        .lines()
-        .enumerate()
-        .map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .filter(|(_, c)| *c == 'S')
-                .map(move |(x, _)| (x as i32, y as i32))
-        })
-        .flatten()
-        .next()
-        .unwrap();
-
-    (field, start)
-}
+// The walk starts from the single `S` of the map
+fn parse(content: &str) -> Result<(Field, Position), Error> {
+    let (field, starts) = garden::parse(content)?;
 
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
-        match value {
-            '#' => Tile::Stone,
-            '.' | 'S' => Tile::Empty,
-            _ => unreachable!(),
-        }
+    match starts[..] {
+        [(x, y)] => Ok((field, (x as i64, y as i64))),
+        _ => Err(anyhow!("map should have one start, found {}", starts.len())),
     }
 }
 
@@ -243,20 +217,19 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content, 6);
+        let result = run(&content, 6).unwrap();
         assert_eq!(result, 16);
 
-        let result = run(&content, 10);
+        let result = run(&content, 10).unwrap();
         assert_eq!(result, 50);
 
-        let result = run(&content, 50);
+        let result = run(&content, 50).unwrap();
         assert_eq!(result, 1594);
 
-        let result = run(&content, 5000);
+        let result = run(&content, 5000).unwrap();
         assert_eq!(result, 16733044);
     }
 
-    /*
     #[test]
     fn test_long() {
         let file = "long_data";
@@ -264,19 +237,24 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content, 64);
-        assert_eq!(result, 807069600)
+        let result = run(&content, 64).unwrap();
+        assert_eq!(result, 3594);
+
+        let result = run(&content, 26501365).unwrap();
+        assert_eq!(result, 605247138198755)
     }
 
-    #[bench]
-    fn bench(b: &mut Bencher) {
+    #[test]
+    fn test_fit_matches_brute_force() {
         let file = "long_data";
         let mut file = File::open(file).unwrap();
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content, 64));
-
+        let (layout, start) = parse(&content).unwrap();
+        for steps in [1000, 1777] {
+            let brute_force = Distances::new(&layout, start, steps).reachable(steps);
+            assert_eq!(extrapolate(&layout, start, steps).unwrap(), brute_force);
+        }
     }
-    */
 }
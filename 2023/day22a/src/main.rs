@@ -1,6 +1,7 @@
-use std::{fs::File, io::Read, str::FromStr, usize};
+use std::{fs::File, io::Read};
 
 use anyhow::Error;
+use aoc2023_day22b::block::{self, Block, Blocks};
 use clap::Parser;
 use iter_tools::Itertools;
 
@@ -11,26 +12,28 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
+    println!("{}", result);
+
+    Ok(())
 }
 
-fn run(content: &str) -> usize {
-    let blocks = parse(content);
+fn run(content: &str) -> Result<usize, Error> {
+    let blocks = block::parse(content)?;
 
     let blocks = settle(blocks);
     let resting = calculate_resting(blocks);
 
-    resting
+    Ok(resting
         .iter()
         .filter(|blocks| {
             blocks.iter().all(|block| {
@@ -41,7 +44,7 @@ fn run(content: &str) -> usize {
                     > 1
             })
         })
-        .count()
+        .count())
 }
 
 fn settle(mut blocks: Blocks) -> Blocks {
@@ -107,60 +110,6 @@ fn bases_intersect(block: &Block, other: &Block) -> bool {
         && block.end.y >= other.start.y
 }
 
-type Blocks = Vec<Block>;
-
-#[derive(Debug)]
-struct Block {
-    start: Coordinates,
-    end: Coordinates,
-}
-
-#[derive(Debug, PartialEq, PartialOrd)]
-struct Coordinates {
-    x: usize,
-    y: usize,
-    z: usize,
-}
-
-// Parsing
-
-fn parse(content: &str) -> Blocks {
-    content
-        .lines()
-        .map(Block::from_str)
-        .collect::<Result<_, _>>()
-        .unwrap()
-}
-
-impl FromStr for Block {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start, end) = s.split_once("~").unwrap();
-
-        let start = Coordinates::from_str(start).unwrap();
-        let end = Coordinates::from_str(end).unwrap();
-
-        assert!(start <= end);
-
-        Ok(Block { start, end })
-    }
-}
-
-impl FromStr for Coordinates {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(",").map(usize::from_str);
-
-        Ok(Coordinates {
-            x: parts.next().unwrap().unwrap(),
-            y: parts.next().unwrap().unwrap(),
-            z: parts.next().unwrap().unwrap(),
-        })
-    }
-}
-
 // testing
 #[cfg(test)]
 mod tests {
@@ -174,7 +123,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 5);
     }
 
@@ -185,7 +134,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 405)
     }
 }
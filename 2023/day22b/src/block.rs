@@ -0,0 +1,86 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+
+pub type Blocks = Vec<Block>;
+
+#[derive(Debug)]
+pub struct Block {
+    pub start: Coordinates,
+    pub end: Coordinates,
+}
+
+#[derive(Debug, PartialEq, PartialOrd)]
+pub struct Coordinates {
+    pub x: usize,
+    pub y: usize,
+    pub z: usize,
+}
+
+// Parsing
+
+pub fn parse(content: &str) -> Result<Blocks, Error> {
+    let blocks: Blocks = content
+        .lines()
+        .map(Block::from_str)
+        .collect::<Result<_, _>>()?;
+
+    if blocks.is_empty() {
+        return Err(anyhow!("snapshot should hold at least one block"));
+    }
+
+    Ok(blocks)
+}
+
+impl FromStr for Block {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('~')
+            .ok_or(anyhow!("block should have the form x,y,z~x,y,z: {}", s))?;
+
+        let start = Coordinates::from_str(start)?;
+        let end = Coordinates::from_str(end)?;
+
+        // Settling and the exports rely on the end being the far corner on every axis
+        if start.x > end.x || start.y > end.y || start.z > end.z {
+            return Err(anyhow!("block should start at its lowest corner: {}", s));
+        }
+
+        Ok(Block { start, end })
+    }
+}
+
+impl FromStr for Coordinates {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .split(',')
+            .map(usize::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match parts[..] {
+            [x, y, z] => Ok(Coordinates { x, y, z }),
+            _ => Err(anyhow!("coordinates should have the form x,y,z: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        assert!(parse("1,0,1~1,2,1").is_ok());
+        assert!(parse("").is_err());
+        assert!(parse("1,0,1").is_err());
+        assert!(parse("1,0~1,2").is_err());
+        assert!(parse("1,0,1~1,2,1,4").is_err());
+        assert!(parse("1,-1,1~1,2,1").is_err());
+        // Ordered as tuples, but not on every axis
+        assert!(parse("0,2,1~1,0,1").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+use std::io::{self, Write};
+
+use aoc2023_day22b::block::{Block, Blocks};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+// Corners of a box are numbered by their offsets as bits: x + 2y + 4z
+const FACES: [([usize; 4], [i32; 3]); 6] = [
+    ([0, 4, 6, 2], [-1, 0, 0]),
+    ([1, 3, 7, 5], [1, 0, 0]),
+    ([0, 1, 5, 4], [0, -1, 0]),
+    ([2, 6, 7, 3], [0, 1, 0]),
+    ([0, 2, 3, 1], [0, 0, -1]),
+    ([4, 5, 7, 6], [0, 0, 1]),
+];
+
+fn corners(block: &Block) -> [[usize; 3]; 8] {
+    let min = [block.start.x, block.start.y, block.start.z];
+    let max = [block.end.x + 1, block.end.y + 1, block.end.z + 1];
+
+    let mut corners = [[0; 3]; 8];
+    for (i, corner) in corners.iter_mut().enumerate() {
+        for (axis, coordinate) in corner.iter_mut().enumerate() {
+            *coordinate = if i & (1 << axis) == 0 {
+                min[axis]
+            } else {
+                max[axis]
+            };
+        }
+    }
+
+    corners
+}
+
+// Green for bricks that can be removed safely, red for the one dropping the most
+fn color(falling: usize, max_falling: usize) -> [f64; 3] {
+    let t = if max_falling == 0 {
+        0.
+    } else {
+        falling as f64 / max_falling as f64
+    };
+
+    [t, 1. - t, 0.2]
+}
+
+pub fn write_obj(mut out: impl Write, blocks: &Blocks, falling: &[usize]) -> io::Result<()> {
+    let max_falling = falling.iter().copied().max().unwrap_or(0);
+
+    for (i, block) in blocks.iter().enumerate() {
+        let [r, g, b] = color(falling[i], max_falling);
+
+        writeln!(out, "o brick_{}", i)?;
+        writeln!(out, "# falling: {}", falling[i])?;
+        for [x, y, z] in corners(block) {
+            writeln!(out, "v {} {} {} {:.3} {:.3} {:.3}", x, y, z, r, g, b)?;
+        }
+        for (face, _) in FACES {
+            let [a, b, c, d] = face.map(|corner| i * 8 + corner + 1);
+            writeln!(out, "f {} {} {} {}", a, b, c, d)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn write_stl(mut out: impl Write, blocks: &Blocks, falling: &[usize]) -> io::Result<()> {
+    for (i, block) in blocks.iter().enumerate() {
+        let name = format!("brick_{}_falling_{}", i, falling[i]);
+        let corners = corners(block);
+
+        writeln!(out, "solid {}", name)?;
+        for ([a, b, c, d], [nx, ny, nz]) in FACES {
+            for triangle in [[a, b, c], [a, c, d]] {
+                writeln!(out, "  facet normal {} {} {}", nx, ny, nz)?;
+                writeln!(out, "    outer loop")?;
+                for [x, y, z] in triangle.map(|corner| corners[corner]) {
+                    writeln!(out, "      vertex {} {} {}", x, y, z)?;
+                }
+                writeln!(out, "    endloop")?;
+                writeln!(out, "  endfacet")?;
+            }
+        }
+        writeln!(out, "endsolid {}", name)?;
+    }
+
+    Ok(())
+}
+
+// View of the stack along the other horizontal axis, bricks are labeled by their index
+pub fn projection(blocks: &Blocks, axis: Axis) -> String {
+    let range = |block: &Block| match axis {
+        Axis::X => (block.start.x, block.end.x),
+        Axis::Y => (block.start.y, block.end.y),
+    };
+
+    let width = blocks.iter().map(|block| range(block).1).max().unwrap_or(0) + 1;
+    let height = blocks.iter().map(|block| block.end.z).max().unwrap_or(0);
+
+    let mut lines = vec![];
+
+    let name = match axis {
+        Axis::X => "x",
+        Axis::Y => "y",
+    };
+    lines.push(format!("{}{}", " ".repeat(width / 2), name));
+    lines.push((0..width).map(|i| (i % 10).to_string()).collect());
+
+    for z in (1..=height).rev() {
+        let row: String = (0..width)
+            .map(|i| {
+                let mut covering = blocks.iter().enumerate().filter(|(_, block)| {
+                    let (start, end) = range(block);
+                    start <= i && i <= end && block.start.z <= z && z <= block.end.z
+                });
+
+                match (covering.next(), covering.next()) {
+                    (None, _) => '.',
+                    (Some((j, _)), None) => (b'A' + (j % 26) as u8) as char,
+                    (Some(_), Some(_)) => '?',
+                }
+            })
+            .collect();
+
+        if z == height.div_ceil(2) {
+            lines.push(format!("{} {} z", row, z));
+        } else {
+            lines.push(format!("{} {}", row, z));
+        }
+    }
+
+    lines.push(format!("{} 0", "-".repeat(width)));
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs::File, io::Read};
+
+    use aoc2023_day22b::block::parse;
+
+    use super::*;
+    use crate::settle;
+
+    fn settled() -> Blocks {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        settle(parse(&content).unwrap())
+    }
+
+    #[test]
+    fn test_projections() {
+        let blocks = settled();
+
+        let front = projection(&blocks, Axis::X);
+        assert_eq!(
+            front,
+            " x\n012\n.G. 6\n.G. 5\nFFF 4\nD.E 3 z\n??? 2\n.A. 1\n--- 0"
+        );
+
+        let side = projection(&blocks, Axis::Y);
+        assert_eq!(
+            side,
+            " y\n012\n.G. 6\n.G. 5\n.F. 4\n??? 3 z\nB.C 2\nAAA 1\n--- 0"
+        );
+    }
+
+    #[test]
+    fn test_obj() {
+        let blocks = settled();
+        let falling = vec![0; blocks.len()];
+
+        let mut out = vec![];
+        write_obj(&mut out, &blocks, &falling).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert_eq!(out.lines().filter(|l| l.starts_with("v ")).count(), 8 * 7);
+        assert_eq!(out.lines().filter(|l| l.starts_with("f ")).count(), 6 * 7);
+    }
+}
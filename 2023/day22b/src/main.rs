@@ -1,7 +1,11 @@
-use std::{collections::HashSet, fs::File, io::Read, str::FromStr, usize};
+mod export;
+
+use std::{fs::File, io::Read};
 
 use anyhow::Error;
+use aoc2023_day22b::block::{self, Blocks};
 use clap::Parser;
+use export::Axis;
 use iter_tools::Itertools;
 
 #[derive(Parser, Debug)]
@@ -9,54 +13,148 @@ use iter_tools::Itertools;
 struct Args {
     #[arg(short, long)]
     file: String,
+    #[arg(long)]
+    obj: Option<String>,
+    #[arg(long)]
+    stl: Option<String>,
+    #[arg(short, long)]
+    projections: bool,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
-    let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(&args.file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
+
+    if args.projections || args.obj.is_some() || args.stl.is_some() {
+        write_exports(&content, &args)?;
+    }
+
+    let result = run(&content)?;
 
-    let result = run(&content);
+    println!("{}", result);
 
-    println!("{}", result)
+    Ok(())
 }
 
-fn run(content: &str) -> usize {
-    let blocks = parse(content);
+fn write_exports(content: &str, args: &Args) -> Result<(), Error> {
+    let blocks = settle(block::parse(content)?);
+    let falling = SupportGraph::new(&blocks).falling();
+
+    if args.projections {
+        println!("{}\n", export::projection(&blocks, Axis::X));
+        println!("{}\n", export::projection(&blocks, Axis::Y));
+    }
+
+    if let Some(obj) = &args.obj {
+        let file = File::create(obj)?;
+        export::write_obj(file, &blocks, &falling)?;
+    }
 
-    let blocks = settle(blocks);
-    let bottoms = blocks
-        .iter()
-        .enumerate()
-        .filter_map(|(i, block)| if block.start.z == 1 { Some(i) } else { None })
-        .collect_vec();
-    let supporting = calculate_supporting(blocks);
+    if let Some(stl) = &args.stl {
+        let file = File::create(stl)?;
+        export::write_stl(file, &blocks, &falling)?;
+    }
 
-    (0..supporting.len())
-        .map(|i| chain(&supporting, &bottoms, i))
-        .sum()
+    Ok(())
 }
 
-fn chain(supporting: &Vec<Vec<usize>>, bottoms: &Vec<usize>, i: usize) -> usize {
-    let mut all_fallen: HashSet<usize> = HashSet::new();
-    let mut just_fallen = HashSet::from([i]);
+fn run(content: &str) -> Result<usize, Error> {
+    let blocks = settle(block::parse(content)?);
+    let graph = SupportGraph::new(&blocks);
 
-    while !just_fallen.is_empty() {
-        all_fallen.extend(just_fallen.into_iter());
+    Ok(graph.falling().into_iter().sum())
+}
 
-        just_fallen = (0..supporting.len())
-            .filter(|id| !all_fallen.contains(id))
-            .filter(|top| {
-                !bottoms.contains(top)
-                    && supporting[*top].iter().all(|bot| all_fallen.contains(bot))
-            })
-            .collect();
+// Node 0 is the ground, every block is a node numbered in order of its settled height,
+// so supports always come before the blocks resting on them
+struct SupportGraph {
+    blocks: Vec<usize>,
+    supporting: Vec<Vec<usize>>,
+}
+
+impl SupportGraph {
+    fn new(blocks: &Blocks) -> Self {
+        let order = (0..blocks.len())
+            .sorted_by_key(|i| blocks[*i].start.z)
+            .collect_vec();
+
+        let width = blocks.iter().map(|block| block.end.x).max().unwrap() + 1;
+        let breadth = blocks.iter().map(|block| block.end.y).max().unwrap() + 1;
+
+        // Topmost node and its height for every column
+        let mut tops = (0..breadth).map(|_| vec![(0, 0); width]).collect_vec();
+
+        let mut supporting = vec![vec![]];
+        for (i, block) in order.iter().map(|i| &blocks[*i]).enumerate() {
+            let node = i + 1;
+
+            let below = (block.start.y..=block.end.y)
+                .flat_map(|y| (block.start.x..=block.end.x).map(move |x| (x, y)))
+                .map(|(x, y)| tops[y][x])
+                .filter(|(_, height)| height + 1 == block.start.z)
+                .map(|(node, _)| node)
+                .unique()
+                .collect_vec();
+            supporting.push(below);
+
+            for row in &mut tops[block.start.y..=block.end.y] {
+                row[block.start.x..=block.end.x].fill((node, block.end.z));
+            }
+        }
+
+        SupportGraph {
+            blocks: order,
+            supporting,
+        }
+    }
+
+    // Immediate dominators with respect to the ground, a block is dominated by every
+    // block whose removal makes it fall
+    fn dominators(&self) -> Vec<usize> {
+        let mut idom = vec![0; self.supporting.len()];
+
+        for node in 1..self.supporting.len() {
+            idom[node] = self.supporting[node]
+                .iter()
+                .copied()
+                .reduce(|a, b| intersect(&idom, a, b))
+                .unwrap();
+        }
+
+        idom
+    }
+
+    // Number of other blocks falling when removing each block, indexed like the blocks
+    fn falling(&self) -> Vec<usize> {
+        let idom = self.dominators();
+
+        let mut dominated = vec![1; idom.len()];
+        for node in (1..idom.len()).rev() {
+            dominated[idom[node]] += dominated[node];
+        }
+
+        let mut falling = vec![0; self.blocks.len()];
+        for (node, block) in self.blocks.iter().enumerate() {
+            falling[*block] = dominated[node + 1] - 1;
+        }
+
+        falling
+    }
+}
+
+fn intersect(idom: &[usize], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        if a > b {
+            a = idom[a];
+        } else {
+            b = idom[b];
+        }
     }
 
-    all_fallen.len() - 1
+    a
 }
 
 fn settle(mut blocks: Blocks) -> Blocks {
@@ -97,88 +195,12 @@ fn settle(mut blocks: Blocks) -> Blocks {
     blocks
 }
 
-fn calculate_supporting(blocks: Blocks) -> Vec<Vec<usize>> {
-    let mut res = vec![];
-
-    for (i, block) in blocks.iter().enumerate() {
-        let mut resting = vec![];
-
-        for (j, other) in blocks.iter().enumerate() {
-            if bases_intersect(other, block) && other.end.z + 1 == block.start.z {
-                resting.push(j);
-            }
-        }
-
-        res.push(resting);
-    }
-
-    res
-}
-
-fn bases_intersect(block: &Block, other: &Block) -> bool {
-    block.start.x <= other.end.x
-        && block.end.x >= other.start.x
-        && block.start.y <= other.end.y
-        && block.end.y >= other.start.y
-}
-
-type Blocks = Vec<Block>;
-
-#[derive(Debug)]
-struct Block {
-    start: Coordinates,
-    end: Coordinates,
-}
-
-#[derive(Debug, PartialEq, PartialOrd)]
-struct Coordinates {
-    x: usize,
-    y: usize,
-    z: usize,
-}
-
-// Parsing
-
-fn parse(content: &str) -> Blocks {
-    content
-        .lines()
-        .map(Block::from_str)
-        .collect::<Result<_, _>>()
-        .unwrap()
-}
-
-impl FromStr for Block {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (start, end) = s.split_once("~").unwrap();
-
-        let start = Coordinates::from_str(start).unwrap();
-        let end = Coordinates::from_str(end).unwrap();
-
-        assert!(start <= end);
-
-        Ok(Block { start, end })
-    }
-}
-
-impl FromStr for Coordinates {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(",").map(usize::from_str);
-
-        Ok(Coordinates {
-            x: parts.next().unwrap().unwrap(),
-            y: parts.next().unwrap().unwrap(),
-            z: parts.next().unwrap().unwrap(),
-        })
-    }
-}
-
 // testing
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
+    use proptest::{collection::vec, prelude::*};
 
     use super::*;
 
@@ -189,7 +211,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 7);
     }
 
@@ -200,7 +222,118 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 61297)
     }
+
+    // Snapshots of straight bricks in a small area, skipping bricks overlapping earlier ones
+    fn snapshot() -> impl Strategy<Value = String> {
+        vec(
+            (0usize..4, 0usize..4, 1usize..12, 0usize..3, 0usize..3),
+            1..12,
+        )
+        .prop_map(|bricks| {
+            let mut taken = HashSet::new();
+            let mut lines = vec![];
+
+            for (x, y, z, axis, length) in bricks {
+                let start = [x, y, z];
+                let mut end = start;
+                end[axis] += length;
+
+                let cells = cells(start, end);
+                if cells.iter().any(|cell| taken.contains(cell)) {
+                    continue;
+                }
+                taken.extend(cells);
+                lines.push(format!(
+                    "{},{},{}~{},{},{}",
+                    start[0], start[1], start[2], end[0], end[1], end[2]
+                ));
+            }
+
+            lines.join("\n")
+        })
+    }
+
+    fn cells(start: [usize; 3], end: [usize; 3]) -> Vec<[usize; 3]> {
+        (start[0]..=end[0])
+            .flat_map(|x| (start[1]..=end[1]).map(move |y| (x, y)))
+            .flat_map(|(x, y)| (start[2]..=end[2]).map(move |z| [x, y, z]))
+            .collect()
+    }
+
+    // Lowers bricks one step at a time until none can move, returns how many moved
+    fn drop_all(bricks: &mut [([usize; 3], [usize; 3])]) -> usize {
+        bricks.sort_by_key(|(start, _)| start[2]);
+
+        let mut taken: HashSet<[usize; 3]> = bricks
+            .iter()
+            .flat_map(|(start, end)| cells(*start, *end))
+            .collect();
+        let mut moved = 0;
+
+        for (start, end) in bricks.iter_mut() {
+            let mut fell = false;
+
+            while start[2] > 1 {
+                let below = cells(
+                    [start[0], start[1], start[2] - 1],
+                    [end[0], end[1], start[2] - 1],
+                );
+                if below.iter().any(|cell| taken.contains(cell)) {
+                    break;
+                }
+
+                for cell in cells(*start, *end) {
+                    taken.remove(&cell);
+                }
+                start[2] -= 1;
+                end[2] -= 1;
+                taken.extend(cells(*start, *end));
+                fell = true;
+            }
+
+            if fell {
+                moved += 1;
+            }
+        }
+
+        moved
+    }
+
+    // Settles the snapshot, then takes out every brick in turn and lets the rest fall again
+    fn brute_force(content: &str) -> usize {
+        let mut bricks = block::parse(content)
+            .unwrap()
+            .iter()
+            .map(|b| {
+                (
+                    [b.start.x, b.start.y, b.start.z],
+                    [b.end.x, b.end.y, b.end.z],
+                )
+            })
+            .collect_vec();
+        drop_all(&mut bricks);
+
+        (0..bricks.len())
+            .map(|i| {
+                let mut rest = bricks.clone();
+                rest.remove(i);
+                drop_all(&mut rest)
+            })
+            .sum()
+    }
+
+    proptest! {
+        #[test]
+        fn test_against_brute_force(content in snapshot()) {
+            prop_assert_eq!(run(&content).unwrap(), brute_force(&content));
+        }
+
+        #[test]
+        fn test_parse_any(line in "\\PC*") {
+            let _ = block::parse(&line);
+        }
+    }
 }
@@ -2,9 +2,10 @@ use core::panic;
 use std::{collections::HashMap, fs::File, io::Read, str::FromStr, usize};
 
 use anyhow::Error;
+use aoc2023_day23b::trails::{Direction, Field, Position, Tile};
 use clap::Parser;
 use iter_tools::Itertools;
-use strum::{EnumIter, IntoEnumIterator};
+use strum::IntoEnumIterator;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -13,21 +14,23 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
+    println!("{}", result);
+
+    Ok(())
 }
 
-fn run(content: &str) -> usize {
-    let field = Field::from_str(content).unwrap();
+fn run(content: &str) -> Result<usize, Error> {
+    let field = Field::from_str(content)?;
 
     let mut seen = vec![];
     let mut graph = HashMap::new();
@@ -42,7 +45,7 @@ fn run(content: &str) -> usize {
     );
 
     let mut seen = vec![];
-    walk_graph(&graph, &field, &mut seen, field.start, 0)
+    Ok(walk_graph(&graph, &field, &mut seen, field.start, 0))
 }
 
 fn walk_graph(
@@ -146,103 +149,6 @@ fn walk_field(
     }
 }
 
-impl Direction {
-    fn apply(&self, (x, y): Position) -> Option<Position> {
-        match self {
-            Direction::Up => y.checked_sub(1).map(|y| (x, y)),
-            Direction::Right => Some((x + 1, y)),
-            Direction::Down => Some((x, y + 1)),
-            Direction::Left => x.checked_sub(1).map(|x| (x, y)),
-        }
-    }
-
-    fn is_opposite(&self, other: &Self) -> bool {
-        *other
-            == match self {
-                Direction::Up => Direction::Down,
-                Direction::Right => Direction::Left,
-                Direction::Down => Direction::Up,
-                Direction::Left => Direction::Right,
-            }
-    }
-}
-
-type Position = (usize, usize);
-
-#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-#[derive(Debug, PartialEq)]
-enum Tile {
-    Path,
-    Forest,
-    Slope(Direction),
-}
-
-#[derive(Debug)]
-struct Field {
-    tiles: Vec<Vec<Tile>>,
-    height: usize,
-    width: usize,
-    start: (usize, usize),
-    end: (usize, usize),
-}
-
-// Parsing
-
-impl FromStr for Field {
-    type Err = Error;
-
-    fn from_str(content: &str) -> Result<Self, Self::Err> {
-        let tiles = content
-            .lines()
-            .map(|line| line.chars().map(Tile::from).collect_vec())
-            .collect_vec();
-
-        let height = tiles.len();
-        let width = tiles[0].len();
-
-        let index_of_only_path = |index: usize| {
-            (
-                tiles[index as usize]
-                    .iter()
-                    .enumerate()
-                    .find_map(|(i, t)| if *t == Tile::Path { Some(i) } else { None })
-                    .expect("expected row to have at least one path tile"),
-                index,
-            )
-        };
-
-        let start = index_of_only_path(0);
-        let end = index_of_only_path(height - 1);
-
-        Ok(Field {
-            tiles,
-            height,
-            width,
-            start,
-            end,
-        })
-    }
-}
-
-impl From<char> for Tile {
-    fn from(value: char) -> Self {
-        match value {
-            '.' => Tile::Path,
-            '#' => Tile::Forest,
-            '>' => Tile::Slope(Direction::Right),
-            'v' => Tile::Slope(Direction::Down),
-            _ => unreachable!("unexpected character in input: {}", value),
-        }
-    }
-}
-
 // testing
 #[cfg(test)]
 mod tests {
@@ -256,7 +162,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 154);
     }
 
@@ -267,7 +173,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 405)
     }
 }
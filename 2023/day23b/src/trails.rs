@@ -0,0 +1,131 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+use strum::EnumIter;
+
+pub type Position = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub fn apply(&self, (x, y): Position) -> Option<Position> {
+        match self {
+            Direction::Up => y.checked_sub(1).map(|y| (x, y)),
+            Direction::Right => Some((x + 1, y)),
+            Direction::Down => Some((x, y + 1)),
+            Direction::Left => x.checked_sub(1).map(|x| (x, y)),
+        }
+    }
+
+    pub fn is_opposite(&self, other: &Self) -> bool {
+        *other
+            == match self {
+                Direction::Up => Direction::Down,
+                Direction::Right => Direction::Left,
+                Direction::Down => Direction::Up,
+                Direction::Left => Direction::Right,
+            }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Tile {
+    Path,
+    Forest,
+    Slope(Direction),
+}
+
+#[derive(Debug)]
+pub struct Field {
+    pub tiles: Vec<Vec<Tile>>,
+    pub height: usize,
+    pub width: usize,
+    pub start: Position,
+    pub end: Position,
+}
+
+// Parsing
+
+// A non empty rectangle of tiles, the hike starts on the first path of the top row and
+// ends on the first path of the bottom row
+impl FromStr for Field {
+    type Err = Error;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let tiles: Vec<Vec<Tile>> = content
+            .lines()
+            .map(|line| line.chars().map(Tile::try_from).collect())
+            .collect::<Result<_, _>>()?;
+
+        let height = tiles.len();
+        let width = tiles.first().map(Vec::len).unwrap_or(0);
+        if width == 0 || tiles.iter().any(|row| row.len() != width) {
+            return Err(anyhow!("map should be a non empty rectangle"));
+        }
+
+        let index_of_only_path = |index: usize| -> Result<Position, Error> {
+            let x = tiles[index]
+                .iter()
+                .position(|t| *t == Tile::Path)
+                .ok_or(anyhow!("row {} should have at least one path tile", index))?;
+            Ok((x, index))
+        };
+
+        let start = index_of_only_path(0)?;
+        let end = index_of_only_path(height - 1)?;
+
+        Ok(Field {
+            tiles,
+            height,
+            width,
+            start,
+            end,
+        })
+    }
+}
+
+impl TryFrom<char> for Tile {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '.' => Ok(Tile::Path),
+            '#' => Ok(Tile::Forest),
+            '>' => Ok(Tile::Slope(Direction::Right)),
+            'v' => Ok(Tile::Slope(Direction::Down)),
+            _ => Err(anyhow!("unexpected character in input: {}", value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let field: Field = "#.#\n#v#\n#.#\n".parse().unwrap();
+        assert_eq!((field.start, field.end), ((1, 0), (1, 2)));
+        assert_eq!(field.tiles[1][1], Tile::Slope(Direction::Down));
+
+        assert!("".parse::<Field>().is_err());
+        assert!("#.#\n#.".parse::<Field>().is_err());
+        assert!("#.#\n#x#\n#.#".parse::<Field>().is_err());
+        assert!("#.#\n#.#\n###".parse::<Field>().is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            let _ = content.parse::<Field>();
+        }
+    }
+}
@@ -0,0 +1,74 @@
+#![feature(test)]
+
+extern crate test;
+
+use std::{fs::File, io::Read};
+
+use anyhow::Error;
+use aoc2023_day24b::hailstone;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[arg(short, long)]
+    file: String,
+
+    /// Lower bound of the test area on both axes
+    #[arg(long, default_value_t = 200000000000000)]
+    min: i64,
+
+    /// Upper bound of the test area on both axes
+    #[arg(long, default_value_t = 400000000000000)]
+    max: i64,
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let file = args.file;
+
+    let mut file = File::open(file)?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+
+    let result = run(&content, args.min, args.max)?;
+
+    println!("{}", result);
+
+    Ok(())
+}
+
+fn run(content: &str, min: i64, max: i64) -> Result<usize, Error> {
+    let hailstones = hailstone::parse(content)?;
+
+    Ok(hailstone::count_crossings(&hailstones, min, max))
+}
+
+// testing
+#[cfg(test)]
+mod tests {
+    use ::test::Bencher;
+
+    use super::*;
+
+    #[test]
+    fn test_short() {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        let result = run(&content, 7, 27).unwrap();
+        assert_eq!(result, 2)
+    }
+
+    #[bench]
+    fn bench(b: &mut Bencher) {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        b.iter(|| run(&content, 200000000000000, 400000000000000));
+    }
+}
@@ -0,0 +1,140 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+use num::{BigInt, BigRational, Zero};
+
+pub type Vector = [i64; 3];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hailstone {
+    pub position: Vector,
+    pub velocity: Vector,
+}
+
+impl Hailstone {
+    // Point where the future paths of both hailstones cross when ignoring z, as exact
+    // fractions. Parallel paths never cross, not even when they lie on the same line.
+    pub fn crossing(&self, other: &Hailstone) -> Option<[BigRational; 2]> {
+        let [px, py, _] = self.position.map(BigInt::from);
+        let [vx, vy, _] = self.velocity.map(BigInt::from);
+        let [qx, qy, _] = other.position.map(BigInt::from);
+        let [wx, wy, _] = other.velocity.map(BigInt::from);
+
+        // p + t * v = q + s * w, solved for t and s by Cramer's rule
+        let det = &wy * &vx - &wx * &vy;
+        if det.is_zero() {
+            return None;
+        }
+
+        let (dx, dy) = (&qx - &px, &qy - &py);
+        let t = BigRational::new(&wy * &dx - &wx * &dy, det.clone());
+        let s = BigRational::new(&vy * &dx - &vx * &dy, det);
+        if t < BigRational::zero() || s < BigRational::zero() {
+            return None;
+        }
+
+        let x = BigRational::from(px) + &t * BigRational::from(vx);
+        let y = BigRational::from(py) + &t * BigRational::from(vy);
+        Some([x, y])
+    }
+}
+
+// Pairs of hailstones whose paths cross inside the square test area, bounds included
+pub fn count_crossings(hailstones: &[Hailstone], min: i64, max: i64) -> usize {
+    let (min, max) = (
+        BigRational::from(BigInt::from(min)),
+        BigRational::from(BigInt::from(max)),
+    );
+
+    hailstones
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| hailstones[i + 1..].iter().map(move |b| (a, b)))
+        .filter_map(|(a, b)| a.crossing(b))
+        .filter(|point| point.iter().all(|c| min <= *c && *c <= max))
+        .count()
+}
+
+impl FromStr for Hailstone {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (position, velocity) = s.split_once('@').ok_or(anyhow!(
+            "hailstone should have the form position @ velocity: {}",
+            s
+        ))?;
+
+        Ok(Hailstone {
+            position: parse_vector(position)?,
+            velocity: parse_vector(velocity)?,
+        })
+    }
+}
+
+fn parse_vector(s: &str) -> Result<Vector, Error> {
+    let coordinates = s
+        .split(',')
+        .map(|c| c.trim().parse())
+        .collect::<Result<Vec<i64>, _>>()?;
+
+    coordinates
+        .try_into()
+        .map_err(|_| anyhow!("vector should have three coordinates: {}", s))
+}
+
+pub fn parse(content: &str) -> Result<Vec<Hailstone>, Error> {
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn hailstone(s: &str) -> Hailstone {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_crossings() {
+        let a = hailstone("19, 13, 30 @ -2, 1, -2");
+
+        let inside = a.crossing(&hailstone("18, 19, 22 @ -1, -1, -2")).unwrap();
+        assert_eq!(
+            inside,
+            [
+                BigRational::new(BigInt::from(43), BigInt::from(3)),
+                BigRational::new(BigInt::from(46), BigInt::from(3)),
+            ]
+        );
+
+        assert!(a.crossing(&hailstone("20, 19, 15 @ 1, -5, -3")).is_none());
+        assert!(hailstone("18, 19, 22 @ -1, -1, -2")
+            .crossing(&hailstone("20, 25, 34 @ -2, -2, -4"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_exact_bounds() {
+        // Both paths reach (10, 10) at t = 10, a boundary f64 could round to either side
+        let a = hailstone("0, 0, 0 @ 1, 1, 0");
+        let b = hailstone("20, 0, 0 @ -1, 1, 0");
+        assert_eq!(count_crossings(&[a, b], 0, 10), 1);
+        assert_eq!(count_crossings(&[a, b], 11, 20), 0);
+
+        let c = hailstone("0, 1, 0 @ 1, 1, 0");
+        assert_eq!(count_crossings(&[a, c], 0, 100), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            let _ = parse(&content);
+        }
+    }
+}
@@ -0,0 +1,261 @@
+#![feature(test)]
+
+extern crate test;
+
+use std::{fs::File, io::Read};
+
+use anyhow::{anyhow, Error};
+use aoc2023_day24b::hailstone::{self, Hailstone, Vector};
+use clap::Parser;
+use num::{BigInt, BigRational, One, Signed, ToPrimitive, Zero};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[arg(short, long)]
+    file: String,
+
+    /// Print the position and velocity of the rock
+    #[arg(short, long)]
+    explain: bool,
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let file = args.file;
+
+    let mut file = File::open(file)?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+
+    let hailstones = hailstone::parse(&content)?;
+    let rock = throw(&hailstones)?;
+
+    if args.explain {
+        let [x, y, z] = rock.position;
+        let [vx, vy, vz] = rock.velocity;
+        println!("rock at {}, {}, {} @ {}, {}, {}", x, y, z, vx, vy, vz);
+    }
+
+    println!("{}", rock.position.iter().sum::<i64>());
+
+    Ok(())
+}
+
+// A rock thrown from P with velocity V hits hailstone i at some time t exactly if
+// P - p_i and V - v_i are parallel, (P - p_i) x (V - v_i) = 0. The only non-linear term
+// P x V is the same for all hailstones, so subtracting the equations of two hailstones
+// i and j leaves three linear ones:
+//
+//   P x (v_j - v_i) + (p_j - p_i) x V = p_j x v_j - p_i x v_i
+//
+// Two such pairs determine the six unknowns, every other hailstone only verifies them.
+fn throw(hailstones: &[Hailstone]) -> Result<Hailstone, Error> {
+    for i in 1..hailstones.len() {
+        for j in i + 1..hailstones.len() {
+            let mut rows = Vec::from(pair_equations(&hailstones[0], &hailstones[i]));
+            rows.extend(pair_equations(&hailstones[0], &hailstones[j]));
+
+            if let Some(solution) = solve(rows) {
+                let rock = to_hailstone(&solution)?;
+                return match hailstones.iter().find(|h| hit_time(&rock, h).is_none()) {
+                    None => Ok(rock),
+                    Some(missed) => Err(anyhow!("no single throw hits {:?}", missed)),
+                };
+            }
+        }
+    }
+
+    Err(anyhow!("hailstones do not determine a unique throw"))
+}
+
+type Row = [BigRational; 7];
+
+fn pair_equations(a: &Hailstone, b: &Hailstone) -> [Row; 3] {
+    let big = |v: Vector| v.map(|c| BigRational::from(BigInt::from(c)));
+    let (p, v, q, w) = (
+        big(a.position),
+        big(a.velocity),
+        big(b.position),
+        big(b.velocity),
+    );
+
+    let d: [BigRational; 3] = std::array::from_fn(|k| &w[k] - &v[k]);
+    let e: [BigRational; 3] = std::array::from_fn(|k| &q[k] - &p[k]);
+    let (qw, pv) = (cross(&q, &w), cross(&p, &v));
+    let rhs: [BigRational; 3] = std::array::from_fn(|k| &qw[k] - &pv[k]);
+
+    let zero = BigRational::zero;
+    [
+        [
+            zero(),
+            d[2].clone(),
+            -&d[1],
+            zero(),
+            -&e[2],
+            e[1].clone(),
+            rhs[0].clone(),
+        ],
+        [
+            -&d[2],
+            zero(),
+            d[0].clone(),
+            e[2].clone(),
+            zero(),
+            -&e[0],
+            rhs[1].clone(),
+        ],
+        [
+            d[1].clone(),
+            -&d[0],
+            zero(),
+            -&e[1],
+            e[0].clone(),
+            zero(),
+            rhs[2].clone(),
+        ],
+    ]
+}
+
+fn cross(a: &[BigRational; 3], b: &[BigRational; 3]) -> [BigRational; 3] {
+    [
+        &a[1] * &b[2] - &a[2] * &b[1],
+        &a[2] * &b[0] - &a[0] * &b[2],
+        &a[0] * &b[1] - &a[1] * &b[0],
+    ]
+}
+
+// Gauss-Jordan elimination of an augmented 6x7 matrix, None if it is singular
+fn solve(mut rows: Vec<Row>) -> Option<Vec<BigRational>> {
+    let n = rows.len();
+
+    for column in 0..n {
+        let pivot = (column..n).find(|r| !rows[*r][column].is_zero())?;
+        rows.swap(column, pivot);
+
+        let factor = BigRational::one() / &rows[column][column];
+        for value in rows[column].iter_mut() {
+            *value = &*value * &factor;
+        }
+
+        for r in 0..n {
+            if r == column || rows[r][column].is_zero() {
+                continue;
+            }
+
+            let factor = rows[r][column].clone();
+            let pivot_row = rows[column].clone();
+            for (value, pivot) in rows[r].iter_mut().zip(&pivot_row) {
+                *value = &*value - &factor * pivot;
+            }
+        }
+    }
+
+    Some(rows.into_iter().map(|row| row[n].clone()).collect())
+}
+
+fn to_hailstone(solution: &[BigRational]) -> Result<Hailstone, Error> {
+    let integer = |value: &BigRational| {
+        value
+            .is_integer()
+            .then(|| value.to_integer().to_i64())
+            .flatten()
+            .ok_or(anyhow!("rock coordinate {} is not a 64 bit integer", value))
+    };
+
+    let values = solution
+        .iter()
+        .map(integer)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Hailstone {
+        position: [values[0], values[1], values[2]],
+        velocity: [values[3], values[4], values[5]],
+    })
+}
+
+// Time at which the rock hits the hailstone, if it ever does
+fn hit_time(rock: &Hailstone, hailstone: &Hailstone) -> Option<BigInt> {
+    let mut time = None;
+
+    for k in 0..3 {
+        let distance = BigInt::from(hailstone.position[k]) - BigInt::from(rock.position[k]);
+        let closing = BigInt::from(rock.velocity[k]) - BigInt::from(hailstone.velocity[k]);
+
+        if closing.is_zero() {
+            if !distance.is_zero() {
+                return None;
+            }
+            continue;
+        }
+
+        if !(&distance % &closing).is_zero() {
+            return None;
+        }
+        let t = distance / closing;
+        if t.is_negative() || time.as_ref().is_some_and(|time| *time != t) {
+            return None;
+        }
+        time = Some(t);
+    }
+
+    // Sharing position and velocity in every axis means they travel together
+    Some(time.unwrap_or_else(BigInt::zero))
+}
+
+// testing
+#[cfg(test)]
+mod tests {
+    use ::test::Bencher;
+
+    use super::*;
+
+    fn run(content: &str) -> i64 {
+        let hailstones = hailstone::parse(content).unwrap();
+        throw(&hailstones).unwrap().position.iter().sum()
+    }
+
+    #[test]
+    fn test_short() {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        let hailstones = hailstone::parse(&content).unwrap();
+        let rock = throw(&hailstones).unwrap();
+        assert_eq!(rock.position, [24, 13, 10]);
+        assert_eq!(rock.velocity, [-3, 1, 2]);
+
+        let times: Vec<BigInt> = hailstones
+            .iter()
+            .map(|h| hit_time(&rock, h).unwrap())
+            .collect();
+        assert_eq!(times, [5, 3, 4, 6, 1].map(BigInt::from));
+
+        assert_eq!(run(&content), 47)
+    }
+
+    #[test]
+    fn test_miss() {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        // The last hailstone moved out of the way of the rock
+        let content = content.replace("20, 19, 15", "20, 19, 16");
+        let hailstones = hailstone::parse(&content).unwrap();
+
+        assert!(throw(&hailstones).is_err());
+    }
+
+    #[bench]
+    fn bench(b: &mut Bencher) {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        b.iter(|| run(&content));
+    }
+}
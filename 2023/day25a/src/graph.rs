@@ -0,0 +1,230 @@
+use std::collections::{BinaryHeap, HashMap};
+
+use anyhow::{anyhow, Error};
+
+// Undirected graph over named nodes, interned to dense ids in order of appearance
+#[derive(Debug, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    neighbours: Vec<Vec<usize>>,
+}
+
+// Minimum cut of a graph, `side` marks the nodes on one of its two sides
+#[derive(Debug)]
+pub struct Cut {
+    pub weight: u64,
+    pub side: Vec<bool>,
+}
+
+impl Cut {
+    pub fn sizes(&self) -> (usize, usize) {
+        let inside = self.side.iter().filter(|inside| **inside).count();
+        (inside, self.side.len() - inside)
+    }
+}
+
+impl Graph {
+    pub fn node(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.neighbours.push(vec![]);
+        id
+    }
+
+    pub fn add_edge(&mut self, a: &str, b: &str) {
+        let (a, b) = (self.node(a), self.node(b));
+        if a != b && !self.neighbours[a].contains(&b) {
+            self.neighbours[a].push(b);
+            self.neighbours[b].push(a);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    // Every edge once, with the smaller id first
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours
+            .iter()
+            .enumerate()
+            .flat_map(|(a, neighbours)| {
+                neighbours
+                    .iter()
+                    .filter(move |b| a < **b)
+                    .map(move |b| (a, *b))
+            })
+    }
+
+    pub fn crossing_edges(&self, cut: &Cut) -> Vec<(usize, usize)> {
+        self.edges()
+            .filter(|(a, b)| cut.side[*a] != cut.side[*b])
+            .collect()
+    }
+
+    // Global minimum cut by Stoer-Wagner. Every phase orders the remaining nodes by how
+    // strongly they are connected to the ones before them. The last node is separated from
+    // the rest by a minimum cut between the last two, which are merged for the next phase.
+    pub fn min_cut(&self) -> Option<Cut> {
+        let n = self.len();
+        if n < 2 {
+            return None;
+        }
+
+        let mut weights: Vec<HashMap<usize, u64>> = self
+            .neighbours
+            .iter()
+            .map(|neighbours| neighbours.iter().map(|b| (*b, 1)).collect())
+            .collect();
+        let mut members: Vec<Vec<usize>> = (0..n).map(|node| vec![node]).collect();
+        let mut active: Vec<usize> = (0..n).collect();
+
+        let mut best: Option<(u64, Vec<usize>)> = None;
+
+        while active.len() > 1 {
+            let mut added = vec![false; n];
+            let mut connection = vec![0; n];
+            let mut queue: BinaryHeap<(u64, usize)> = active.iter().map(|v| (0, *v)).collect();
+            let mut order = Vec::with_capacity(active.len());
+
+            while let Some((weight, node)) = queue.pop() {
+                if added[node] || weight != connection[node] {
+                    continue;
+                }
+                added[node] = true;
+                order.push(node);
+
+                for (next, w) in &weights[node] {
+                    if !added[*next] {
+                        connection[*next] += w;
+                        queue.push((connection[*next], *next));
+                    }
+                }
+            }
+
+            let (s, t) = match order[..] {
+                [.., s, t] => (s, t),
+                _ => unreachable!("every phase orders at least two nodes"),
+            };
+
+            let cut_of_phase = connection[t];
+            let improves = match &best {
+                Some((weight, _)) => cut_of_phase < *weight,
+                None => true,
+            };
+            if improves {
+                best = Some((cut_of_phase, members[t].clone()));
+            }
+
+            // Merge t into s
+            let merged = std::mem::take(&mut weights[t]);
+            for (node, w) in merged {
+                weights[node].remove(&t);
+                if node != s {
+                    *weights[s].entry(node).or_insert(0) += w;
+                    *weights[node].entry(s).or_insert(0) += w;
+                }
+            }
+            let moved = std::mem::take(&mut members[t]);
+            members[s].extend(moved);
+            active.retain(|node| *node != t);
+        }
+
+        best.map(|(weight, nodes)| {
+            let mut side = vec![false; n];
+            for node in nodes {
+                side[node] = true;
+            }
+            Cut { weight, side }
+        })
+    }
+}
+
+// Parsing
+
+// Every line names a component followed by the ones it is wired to, wires go both ways
+pub fn parse(content: &str) -> Result<Graph, Error> {
+    let mut graph = Graph::default();
+
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let (component, connected) = line.split_once(':').ok_or(anyhow!(
+            "line should have the form component: components: {}",
+            line
+        ))?;
+
+        let component = component.trim();
+        graph.node(component);
+        for other in connected.split_ascii_whitespace() {
+            graph.add_edge(component, other);
+        }
+    }
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_two_squares() {
+        // Two complete graphs of four nodes each joined by two edges
+        let mut graph = Graph::default();
+        for square in [["a", "b", "c", "d"], ["w", "x", "y", "z"]] {
+            for i in 0..4 {
+                for j in i + 1..4 {
+                    graph.add_edge(square[i], square[j]);
+                }
+            }
+        }
+        graph.add_edge("a", "w");
+        graph.add_edge("d", "z");
+
+        let cut = graph.min_cut().unwrap();
+        assert_eq!(cut.weight, 2);
+        assert_eq!(cut.sizes(), (4, 4));
+
+        let mut edges: Vec<(&str, &str)> = graph
+            .crossing_edges(&cut)
+            .into_iter()
+            .map(|(a, b)| (graph.name(a), graph.name(b)))
+            .collect();
+        edges.sort();
+        assert_eq!(edges, vec![("a", "w"), ("d", "z")]);
+    }
+
+    #[test]
+    fn test_disconnected() {
+        let mut graph = Graph::default();
+        graph.add_edge("a", "b");
+        graph.add_edge("c", "d");
+
+        assert_eq!(graph.min_cut().unwrap().weight, 0);
+        assert!(Graph::default().min_cut().is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            if let Ok(graph) = parse(&content) {
+                let _ = graph.min_cut();
+            }
+        }
+    }
+}
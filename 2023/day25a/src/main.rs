@@ -0,0 +1,114 @@
+#![feature(test)]
+
+extern crate test;
+
+use std::{fs::File, io::Read};
+
+use anyhow::{anyhow, Error};
+use aoc2023_day25a::graph::{self, Graph};
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[arg(short, long)]
+    file: String,
+
+    /// Number of wires the minimum cut has to consist of
+    #[arg(short, long, default_value_t = 3)]
+    wires: u64,
+
+    /// Print the wires to disconnect
+    #[arg(short, long)]
+    explain: bool,
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let file = args.file;
+
+    let mut file = File::open(file)?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+
+    let graph = graph::parse(&content)?;
+    let (product, wires) = split(&graph, args.wires)?;
+
+    if args.explain {
+        for (a, b) in wires {
+            println!("disconnect {}/{}", a, b);
+        }
+    }
+
+    println!("{}", product);
+
+    Ok(())
+}
+
+type Wire<'a> = (&'a str, &'a str);
+
+// Product of the sizes of both groups and the wires between them, sorted by name
+fn split(graph: &Graph, wires: u64) -> Result<(usize, Vec<Wire<'_>>), Error> {
+    let cut = graph
+        .min_cut()
+        .ok_or(anyhow!("at least two components are needed to split"))?;
+
+    if cut.weight != wires {
+        return Err(anyhow!(
+            "minimum cut has {} wires instead of {}",
+            cut.weight,
+            wires
+        ));
+    }
+
+    let mut edges: Vec<Wire> = graph
+        .crossing_edges(&cut)
+        .into_iter()
+        .map(|(a, b)| {
+            let (a, b) = (graph.name(a), graph.name(b));
+            (a.min(b), a.max(b))
+        })
+        .collect();
+    edges.sort();
+
+    let (inside, outside) = cut.sizes();
+    Ok((inside * outside, edges))
+}
+
+// testing
+#[cfg(test)]
+mod tests {
+    use ::test::Bencher;
+
+    use super::*;
+
+    fn run(content: &str) -> usize {
+        let graph = graph::parse(content).unwrap();
+        split(&graph, 3).unwrap().0
+    }
+
+    #[test]
+    fn test_short() {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        let graph = graph::parse(&content).unwrap();
+        let (product, wires) = split(&graph, 3).unwrap();
+        assert_eq!(product, 54);
+        assert_eq!(wires, [("bvb", "cmg"), ("hfx", "pzl"), ("jqt", "nvd")]);
+
+        assert!(split(&graph, 4).is_err());
+    }
+
+    #[bench]
+    fn bench(b: &mut Bencher) {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        b.iter(|| run(&content));
+    }
+}
@@ -0,0 +1,305 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use anyhow::{anyhow, Error};
+use regex::Regex;
+
+// Number of cubes per colour, colours are whatever the input names
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cubes(pub BTreeMap<String, u32>);
+
+impl Cubes {
+    pub fn get(&self, colour: &str) -> u32 {
+        self.0.get(colour).copied().unwrap_or(0)
+    }
+
+    // Product of the counts of the given colours, missing ones count as zero
+    pub fn power<'a>(&self, colours: impl IntoIterator<Item = &'a String>) -> Result<u64, Error> {
+        colours
+            .into_iter()
+            .try_fold(1u64, |product, colour| {
+                product.checked_mul(self.get(colour).into())
+            })
+            .ok_or(anyhow!("power of {} overflows", self))
+    }
+
+    // Limits either in the same form as a round or as lines of `colour = count`
+    pub fn load(spec: &str) -> Result<Self, Error> {
+        if spec.contains('=') {
+            let mut cubes = Cubes::default();
+            for line in spec.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+
+                let (colour, count) = line.split_once('=').ok_or(anyhow!(
+                    "limit should have the form colour = count: {}",
+                    line
+                ))?;
+                cubes
+                    .0
+                    .insert(colour.trim().to_string(), count.trim().parse()?);
+            }
+            Ok(cubes)
+        } else {
+            spec.parse()
+        }
+    }
+}
+
+// A round like `3 blue, 4 red`
+impl FromStr for Cubes {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cubes = Cubes::default();
+
+        for part in s.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+            let (count, colour) = part
+                .split_once(' ')
+                .ok_or(anyhow!("expected a count and a colour: {}", part))?;
+            let count: u32 = count.parse()?;
+
+            let entry = cubes.0.entry(colour.trim().to_string()).or_insert(0);
+            *entry = entry.checked_add(count).ok_or(anyhow!(
+                "too many {} cubes: {}",
+                colour.trim(),
+                s
+            ))?;
+        }
+
+        Ok(cubes)
+    }
+}
+
+impl Display for Cubes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self
+            .0
+            .iter()
+            .map(|(colour, count)| format!("{} {}", count, colour))
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[derive(Debug)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Cubes>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Violation {
+    // Index of the round, from 1
+    pub round: usize,
+    pub colour: String,
+    pub count: u32,
+    pub limit: u32,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "round {} shows {} {} but the bag holds {}",
+            self.round, self.count, self.colour, self.limit
+        )
+    }
+}
+
+impl Game {
+    // Every colour of every round that exceeds the bag, colours missing from it hold zero
+    pub fn violations(&self, limits: &Cubes) -> Vec<Violation> {
+        self.rounds
+            .iter()
+            .enumerate()
+            .flat_map(|(i, round)| {
+                round
+                    .0
+                    .iter()
+                    .filter(|(colour, count)| **count > limits.get(colour))
+                    .map(move |(colour, count)| Violation {
+                        round: i + 1,
+                        colour: colour.clone(),
+                        count: *count,
+                        limit: limits.get(colour),
+                    })
+            })
+            .collect()
+    }
+
+    pub fn possible(&self, limits: &Cubes) -> bool {
+        self.violations(limits).is_empty()
+    }
+
+    // Smallest bag every round could have been drawn from
+    pub fn minimal_bag(&self) -> Cubes {
+        let mut bag = Cubes::default();
+        for round in &self.rounds {
+            for (colour, count) in &round.0 {
+                let entry = bag.0.entry(colour.clone()).or_insert(0);
+                *entry = (*entry).max(*count);
+            }
+        }
+        bag
+    }
+
+    // Whether the game fits the bag, what went wrong if not, and the bag it needs
+    pub fn report(&self, limits: &Cubes) -> String {
+        let violations = self.violations(limits);
+        let verdict = if violations.is_empty() {
+            "possible".to_string()
+        } else {
+            let reasons: Vec<String> = violations.iter().map(ToString::to_string).collect();
+            format!("impossible, {}", reasons.join("; "))
+        };
+
+        format!(
+            "Game {}: {}; minimal bag {}",
+            self.id,
+            verdict,
+            self.minimal_bag()
+        )
+    }
+}
+
+impl TryFrom<&str> for Game {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (id_part, rounds_part) = value.split_once(':').ok_or(anyhow!(
+            "first split of the game line was not successful: {}",
+            value
+        ))?;
+
+        let game_id_pattern = Regex::new("^Game (\\d+)$").expect("must compile");
+        let captured = game_id_pattern
+            .captures(id_part.trim())
+            .ok_or(anyhow!("invalid game id part: {}", id_part))?;
+        let id = captured[1].parse()?;
+
+        let rounds = rounds_part
+            .split(';')
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Game { id, rounds })
+    }
+}
+
+pub fn parse(content: &str) -> Result<Vec<Game>, Error> {
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(Game::try_from)
+        .collect()
+}
+
+// Sum of the ids of the games the bag could have played
+pub fn possible_ids(games: &[Game], limits: &Cubes) -> u64 {
+    games
+        .iter()
+        .filter(|game| game.possible(limits))
+        .map(|game| u64::from(game.id))
+        .sum()
+}
+
+// Sum of the powers of the smallest bags of the games, over every colour they show
+pub fn total_power(games: &[Game]) -> Result<u64, Error> {
+    let colours = colours(games);
+    games.iter().try_fold(0u64, |sum, game| {
+        sum.checked_add(game.minimal_bag().power(&colours)?)
+            .ok_or(anyhow!("sum of the powers overflows"))
+    })
+}
+
+// Every colour any round of any game shows
+pub fn colours(games: &[Game]) -> Vec<String> {
+    let mut colours: Vec<String> = games
+        .iter()
+        .flat_map(|game| game.rounds.iter().flat_map(|round| round.0.keys().cloned()))
+        .collect();
+    colours.sort();
+    colours.dedup();
+    colours
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const SAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn test_feasibility() {
+        let games = parse(SAMPLE).unwrap();
+        let limits = Cubes::load("12 red, 13 green, 14 blue").unwrap();
+
+        assert_eq!(possible_ids(&games, &limits), 8);
+
+        assert_eq!(
+            games[2].violations(&limits),
+            vec![Violation {
+                round: 1,
+                colour: "red".to_string(),
+                count: 20,
+                limit: 12,
+            }]
+        );
+        assert_eq!(games[3].violations(&limits)[0].colour, "blue");
+        assert_eq!(
+            games[2].report(&limits),
+            "Game 3: impossible, round 1 shows 20 red but the bag holds 12; \
+             minimal bag 6 blue, 13 green, 20 red"
+        );
+
+        let config = Cubes::load("# the bag\nred = 12\ngreen = 13\nblue = 14\n").unwrap();
+        assert_eq!(config, limits);
+    }
+
+    #[test]
+    fn test_minimal_bags() {
+        let games = parse(SAMPLE).unwrap();
+
+        assert_eq!(games[0].minimal_bag().to_string(), "6 blue, 2 green, 4 red");
+
+        assert_eq!(total_power(&games).unwrap(), 2286);
+
+        let purple = parse("Game 7: 2 purple, 1 red; 3 purple").unwrap();
+        assert_eq!(purple[0].minimal_bag().to_string(), "3 purple, 1 red");
+        assert!(!purple[0].possible(&Cubes::load("12 red").unwrap()));
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert!("4294967295 red, 1 red".parse::<Cubes>().is_err());
+
+        let big: Cubes = "4294967295 red, 4294967295 green".parse().unwrap();
+        assert_eq!(
+            big.power(&big.0.keys().cloned().collect::<Vec<_>>())
+                .unwrap(),
+            18446744065119617025
+        );
+        let bigger: Cubes = "4294967295 red, 4294967295 green, 2 blue".parse().unwrap();
+        assert!(bigger
+            .power(&bigger.0.keys().cloned().collect::<Vec<_>>())
+            .is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            if let Ok(games) = parse(&content) {
+                let _ = total_power(&games);
+            }
+            let _ = Cubes::load(&content);
+        }
+    }
+}
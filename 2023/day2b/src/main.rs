@@ -1,118 +1,51 @@
-use std::{fs::File, io::Read};
+use std::{fs, fs::File, io::Read};
 
-use anyhow::{anyhow, Error};
-use regex::Regex;
+use anyhow::Error;
+use aoc2023_day2b::bag::{self, Cubes};
+use clap::Parser;
 
-fn main() {
-    let mut file = File::open("data").unwrap();
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[arg(short, long, default_value = "data")]
+    file: String,
 
-    let mut content: String = "".to_string();
-    file.read_to_string(&mut content).unwrap();
-
-    let lines = content.lines();
-    let games: Vec<Game> = lines
-        .map(|l| l.try_into())
-        .collect::<Result<_, _>>()
-        .unwrap();
+    /// Cubes in the bag, like the rounds of a game
+    #[arg(short, long, default_value = "12 red, 13 green, 14 blue")]
+    limits: String,
 
-    let result: u32 = games
-        .iter()
-        .map(Game::minimum_numbers)
-        .map(MinimumGame::multiply_numbers)
-        .sum();
-
-    println!("result: {}", result)
-}
+    /// File with lines of `colour = count`, replacing --limits
+    #[arg(short, long)]
+    config: Option<String>,
 
-#[derive(Debug)]
-struct Game {
-    rounds: Vec<Round>,
+    /// Print for every game whether it is possible and the bag it needs
+    #[arg(short, long)]
+    report: bool,
 }
 
-#[derive(Debug)]
-struct MinimumGame {
-    r: u32,
-    g: u32,
-    b: u32,
-}
-
-#[derive(Debug)]
-struct Round {
-    r: u32,
-    g: u32,
-    b: u32,
-}
-
-impl Game {
-    fn minimum_numbers(&self) -> MinimumGame {
-        let (mut r, mut g, mut b) = (0, 0, 0);
-
-        for round in &self.rounds {
-            if round.r > r {
-                r = round.r
-            }
-            if round.g > g {
-                g = round.g
-            }
-            if round.b > b {
-                b = round.b
-            }
-        }
-
-        MinimumGame { r, g, b }
-    }
-}
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
 
-impl MinimumGame {
-    fn multiply_numbers(self) -> u32 {
-        self.r * self.g * self.b
-    }
-}
+    let mut file = File::open(&args.file)?;
+    let mut content: String = "".to_string();
+    file.read_to_string(&mut content)?;
 
-// Parsing
+    let limits = match &args.config {
+        Some(path) => Cubes::load(&fs::read_to_string(path)?)?,
+        None => Cubes::load(&args.limits)?,
+    };
 
-impl TryFrom<&str> for Game {
-    type Error = Error;
+    let games = bag::parse(&content)?;
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let parts: Vec<&str> = value.split(":").collect();
-        if parts.len() != 2 {
-            return Err(anyhow!(
-                "first split of the game line was not successful: {}",
-                value
-            ));
+    if args.report {
+        for game in &games {
+            println!("{}", game.report(&limits));
         }
-
-        let rounds = Self::parse_rounds(parts[1])?;
-
-        Ok(Game { rounds })
-    }
-}
-
-impl Game {
-    fn parse_rounds(input: &str) -> Result<Vec<Round>, Error> {
-        input.split(";").map(|r| r.try_into()).collect()
     }
-}
 
-impl TryFrom<&str> for Round {
-    type Error = Error;
+    let result = bag::total_power(&games)?;
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let (mut r, mut g, mut b) = (0, 0, 0);
+    println!("result: {}", result);
 
-        let no_commas = value.replace(",", "");
-        let parts: Vec<&str> = no_commas.trim().split(" ").collect();
-        for chunk in parts.chunks(2) {
-            let val: u32 = chunk[0].parse()?;
-            match chunk[1] {
-                "red" => r = val,
-                "blue" => b = val,
-                "green" => g = val,
-                _ => return Err(anyhow!("expected color here: {}", chunk[1])),
-            }
-        }
-
-        Ok(Round { r, g, b })
-    }
+    Ok(())
 }
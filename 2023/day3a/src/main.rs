@@ -2,82 +2,57 @@
 
 extern crate test;
 
-use std::{fs::File, io::Read, u32};
+use std::{fs::File, io::Read};
 
+use anyhow::Error;
+use aoc2023_day3b::schematic::Schematic;
 use clap::Parser;
-use regex::Regex;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[arg(short, long)]
     file: String,
+
+    /// Only count numbers adjacent to this kind of symbol
+    #[arg(short, long)]
+    symbol: Option<char>,
+
+    /// List the numbers adjacent to no symbol
+    #[arg(short, long)]
+    isolated: bool,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
-
-    let result = run(&content);
-
-    println!("{}", result)
-}
-
-fn run(content: &str) -> u32 {
-    let sym_regex = Regex::new("[^\\.\\d]").unwrap();
-    let symbol_positions = get_symbol_positions(content, &sym_regex);
-
-    let num_regex = Regex::new("\\d+").unwrap();
-    content
-        .lines()
-        .enumerate()
-        .map(|(i, l)| process_line(i, l, &num_regex, &symbol_positions))
-        .sum()
-}
-
-fn get_symbol_positions(content: &str, sym_regex: &Regex) -> Vec<Vec<usize>> {
-    let mut positions = vec![];
+    file.read_to_string(&mut content)?;
 
-    for line in content.lines() {
-        let mut line_positions = vec![];
+    let schematic: Schematic = content.parse()?;
 
-        for sym_match in sym_regex.find_iter(line) {
-            line_positions.push(sym_match.start());
+    if args.isolated {
+        for number in schematic.isolated() {
+            println!(
+                "{} at row {}, columns {}..{}",
+                number.value, number.row, number.start, number.end
+            );
         }
-
-        positions.push(line_positions);
     }
 
-    positions
-}
+    let result: u64 = match args.symbol {
+        Some(kind) => schematic
+            .parts_adjacent_to(kind)
+            .map(|n| u64::from(n.value))
+            .sum(),
+        None => schematic.parts().map(|n| u64::from(n.value)).sum(),
+    };
 
-fn process_line(
-    i: usize,
-    line: &str,
-    num_regex: &Regex,
-    symbol_positions: &Vec<Vec<usize>>,
-) -> u32 {
-    let mut positions = symbol_positions[i].clone();
-    if i > 0 {
-        positions.extend_from_slice(&symbol_positions[i - 1])
-    }
-    if i < symbol_positions.len() - 1 {
-        positions.extend_from_slice(&symbol_positions[i + 1])
-    }
+    println!("{}", result);
 
-    num_regex
-        .find_iter(line)
-        .filter(|m| {
-            positions
-                .iter()
-                .any(|p| if m.start() == 0 { true } else { *p >= m.start() - 1 } && *p <= m.end())
-        })
-        .map(|m| m.as_str().parse::<u32>().expect("match is only digits"))
-        .sum()
+    Ok(())
 }
 
 #[cfg(test)]
@@ -86,6 +61,15 @@ mod tests {
 
     use super::*;
 
+    fn run(content: &str) -> u64 {
+        let schematic: Schematic = content.parse().expect("schematic should be parsable");
+
+        schematic
+            .parts()
+            .map(|number| u64::from(number.value))
+            .sum()
+    }
+
     #[test]
     fn test_short() {
         let file = "short_data";
@@ -2,86 +2,52 @@
 
 extern crate test;
 
-use std::{fs::File, io::Read, u32};
+use std::{fs::File, io::Read};
 
+use anyhow::{anyhow, Error};
+use aoc2023_day3b::schematic::Schematic;
 use clap::Parser;
-use regex::{Match, Regex};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[arg(short, long)]
     file: String,
+
+    /// Symbol marking a gear
+    #[arg(short, long, default_value_t = '*')]
+    gear: char,
+
+    /// Number of part numbers a gear is adjacent to
+    #[arg(short, long, default_value_t = 2)]
+    neighbours: usize,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
-
-    println!("{}", result)
-}
+    let result = run(&content, args.gear, args.neighbours)?;
 
-fn run(content: &str) -> u32 {
-    let num_regex = Regex::new("\\d+").unwrap();
-    let number_matches = get_number_positions(content, &num_regex);
+    println!("{}", result);
 
-    content
-        .lines()
-        .enumerate()
-        .map(|(i, l)| process_line(i, l, &number_matches))
-        .sum()
+    Ok(())
 }
 
-fn get_number_positions<'a>(content: &'a str, num_regex: &'a Regex) -> Vec<Vec<Match<'a>>> {
-    let mut positions = vec![];
-
-    for line in content.lines() {
-        let mut line_positions = vec![];
-
-        for num_match in num_regex.find_iter(line) {
-            line_positions.push(num_match);
-        }
-
-        positions.push(line_positions);
-    }
-
-    positions
-}
-
-fn process_line(i: usize, line: &str, number_matches: &Vec<Vec<Match>>) -> u32 {
-    let mut num_matches = number_matches[i].clone();
-    if i > 0 {
-        num_matches.extend_from_slice(&number_matches[i - 1])
-    }
-    if i < number_matches.len() - 1 {
-        num_matches.extend_from_slice(&number_matches[i + 1])
-    }
+fn run(content: &str, gear: char, neighbours: usize) -> Result<u64, Error> {
+    let schematic: Schematic = content.parse()?;
 
-    line.chars()
-        .enumerate()
-        .filter(|(_, c)| *c == '*')
-        .filter_map(|(i, _)| {
-            let adj_num_matches : Vec<&Match<'_>> = num_matches.iter()
-            .filter(|m| if m.start() == 0 { true } else { i >= m.start() - 1 } && i <= m.end()).collect();
-
-            match adj_num_matches.len() {
-                2 => {
-                    let a = adj_num_matches[0].as_str().parse::<u32>().expect("match is only digits");
-                    let b = adj_num_matches[1].as_str().parse::<u32>().expect("match is only digits");
-
-                    Some(a * b)
-                },
-                _ => None,
-                
-            }
-        })
-        .sum()
+    let sum = schematic
+        .gears(gear, neighbours)
+        .try_fold(0u64, |sum, gear| {
+            sum.checked_add(schematic.ratio(gear)?)
+                .ok_or(anyhow!("sum of the ratios overflows"))
+        });
+    sum
 }
 
 #[cfg(test)]
@@ -97,7 +63,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content, '*', 2).unwrap();
         assert_eq!(result, 467835)
     }
 
@@ -108,7 +74,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content, '*', 2).unwrap();
         assert_eq!(result, 78915902)
     }
 
@@ -119,6 +85,6 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content));
+        b.iter(|| run(&content, '*', 2));
     }
 }
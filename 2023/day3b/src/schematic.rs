@@ -0,0 +1,207 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+
+#[derive(Debug)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    // Columns of the digits, end exclusive
+    pub start: usize,
+    pub end: usize,
+    // Indices of the adjacent symbols
+    pub symbols: Vec<usize>,
+}
+
+#[derive(Debug)]
+pub struct Symbol {
+    pub kind: char,
+    pub row: usize,
+    pub column: usize,
+    // Indices of the adjacent numbers
+    pub numbers: Vec<usize>,
+}
+
+// Numbers and symbols of an engine schematic, linked in both directions when they touch,
+// including diagonally
+#[derive(Debug)]
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+}
+
+impl Schematic {
+    // Numbers adjacent to at least one symbol
+    pub fn parts(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .filter(|number| !number.symbols.is_empty())
+    }
+
+    pub fn parts_adjacent_to(&self, kind: char) -> impl Iterator<Item = &Number> {
+        self.numbers.iter().filter(move |number| {
+            number
+                .symbols
+                .iter()
+                .any(|symbol| self.symbols[*symbol].kind == kind)
+        })
+    }
+
+    // Symbols of the given kind with exactly `neighbours` adjacent numbers
+    pub fn gears(&self, kind: char, neighbours: usize) -> impl Iterator<Item = &Symbol> {
+        self.symbols
+            .iter()
+            .filter(move |symbol| symbol.kind == kind && symbol.numbers.len() == neighbours)
+    }
+
+    pub fn isolated(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .filter(|number| number.symbols.is_empty())
+    }
+
+    pub fn ratio(&self, symbol: &Symbol) -> Result<u64, Error> {
+        symbol
+            .numbers
+            .iter()
+            .try_fold(1u64, |product, number| {
+                product.checked_mul(self.numbers[*number].value.into())
+            })
+            .ok_or(anyhow!(
+                "ratio of the {} at row {}, column {} overflows",
+                symbol.kind,
+                symbol.row,
+                symbol.column
+            ))
+    }
+}
+
+impl FromStr for Schematic {
+    type Err = Error;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let grid: Vec<Vec<char>> = content.lines().map(|l| l.chars().collect()).collect();
+
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        let mut symbol_at: Vec<Vec<Option<usize>>> = vec![];
+
+        for (row, line) in grid.iter().enumerate() {
+            let mut symbol_row = vec![None; line.len()];
+            let mut column = 0;
+
+            while column < line.len() {
+                let c = line[column];
+                if c.is_ascii_digit() {
+                    let start = column;
+                    while column < line.len() && line[column].is_ascii_digit() {
+                        column += 1;
+                    }
+
+                    let digits: String = line[start..column].iter().collect();
+                    let value = digits
+                        .parse()
+                        .map_err(|e| anyhow!("number {} in row {}: {}", digits, row, e))?;
+                    numbers.push(Number {
+                        value,
+                        row,
+                        start,
+                        end: column,
+                        symbols: vec![],
+                    });
+                    continue;
+                }
+
+                if c != '.' {
+                    symbol_row[column] = Some(symbols.len());
+                    symbols.push(Symbol {
+                        kind: c,
+                        row,
+                        column,
+                        numbers: vec![],
+                    });
+                }
+                column += 1;
+            }
+
+            symbol_at.push(symbol_row);
+        }
+
+        for (i, number) in numbers.iter_mut().enumerate() {
+            let rows = number.row.saturating_sub(1)..=number.row + 1;
+            for row in rows.filter_map(|row| symbol_at.get(row)) {
+                let columns = number.start.saturating_sub(1)..=number.end;
+                for symbol in columns.filter_map(|column| row.get(column).copied().flatten()) {
+                    number.symbols.push(symbol);
+                    symbols[symbol].numbers.push(i);
+                }
+            }
+        }
+
+        Ok(Schematic { numbers, symbols })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs::File, io::Read};
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn schematic() -> Schematic {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        content.parse().unwrap()
+    }
+
+    fn values<'a>(numbers: impl Iterator<Item = &'a Number>) -> Vec<u32> {
+        numbers.map(|number| number.value).collect()
+    }
+
+    #[test]
+    fn test_queries() {
+        let schematic = schematic();
+
+        assert_eq!(values(schematic.isolated()), vec![114, 58]);
+        assert_eq!(values(schematic.parts_adjacent_to('#')), vec![633]);
+        assert_eq!(values(schematic.parts_adjacent_to('$')), vec![664]);
+        assert_eq!(values(schematic.parts_adjacent_to('%')), vec![]);
+
+        let gears: Vec<u64> = schematic
+            .gears('*', 2)
+            .map(|gear| schematic.ratio(gear).unwrap())
+            .collect();
+        assert_eq!(gears, vec![16345, 451490]);
+
+        let lonely: Vec<u64> = schematic
+            .gears('*', 1)
+            .map(|gear| schematic.ratio(gear).unwrap())
+            .collect();
+        assert_eq!(lonely, vec![617]);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let schematic: Schematic = "4294967295*4294967295\n...........4294967295"
+            .parse()
+            .unwrap();
+        let gear = schematic.gears('*', 3).next().unwrap();
+        assert!(schematic.ratio(gear).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            if let Ok(schematic) = content.parse::<Schematic>() {
+                for symbol in &schematic.symbols {
+                    let _ = schematic.ratio(symbol);
+                }
+            }
+        }
+    }
+}
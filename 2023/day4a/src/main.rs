@@ -2,9 +2,10 @@
 
 extern crate test;
 
-use std::{fs::File, io::Read, u32};
+use std::{fs::File, io::Read};
 
 use anyhow::{anyhow, Error};
+use aoc2023_day4b::scratchcards;
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -14,84 +15,28 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
-}
-
-fn run(content: &str) -> u32 {
-    content.lines().map(|l| process_line(l)).sum()
-}
+    println!("{}", result);
 
-fn process_line(line: &str) -> u32 {
-    let card: Card = line.try_into().unwrap();
-
-    let wins = card.wins();
-    if wins > 0 {
-        2u32.pow(wins - 1)
-    } else {
-        0
-    }
+    Ok(())
 }
 
-#[derive(Debug)]
-struct Card {
-    winning: Vec<u32>,
-    have: Vec<u32>,
-}
-
-impl Card {
-    fn wins(&self) -> u32 {
-        self.have
-            .iter()
-            .filter(|n| self.winning.contains(n))
-            .count() as u32
-    }
-}
-
-// Parsing
-
-impl TryFrom<&str> for Card {
-    type Error = Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let parts: Vec<&str> = value.split(":").collect();
-        if parts.len() != 2 {
-            return Err(anyhow!(
-                "first split of the card line was not successful: {}",
-                value
-            ));
-        }
-
-        let number_lists: Vec<&str> = parts[1].split("|").collect();
-        if number_lists.len() != 2 {
-            return Err(anyhow!(
-                "second split of the card line was not successful: {}",
-                parts[1]
-            ));
-        }
-
-        let winning = convert_number_list(number_lists[0]);
-        let have = convert_number_list(number_lists[1]);
-
-        Ok(Card { winning, have })
-    }
-}
+fn run(content: &str) -> Result<u64, Error> {
+    let cards = scratchcards::parse(content)?;
 
-fn convert_number_list(list: &str) -> Vec<u32> {
-    list.trim()
-        .split(" ")
-        .filter(|n| !n.is_empty())
-        .map(|n| n.parse::<u32>().expect("parts should be all digits"))
-        .collect()
+    cards.iter().try_fold(0u64, |sum, card| {
+        sum.checked_add(card.points()?)
+            .ok_or(anyhow!("sum of the points overflows"))
+    })
 }
 
 // testing
@@ -109,7 +54,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 13)
     }
 
@@ -120,7 +65,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 18619)
     }
 
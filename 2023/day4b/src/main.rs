@@ -2,9 +2,10 @@
 
 extern crate test;
 
-use std::{fs::File, io::Read, u32};
+use std::{fs::File, io::Read};
 
-use anyhow::{anyhow, Error};
+use anyhow::Error;
+use aoc2023_day4b::scratchcards::{self, Cascade};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -12,85 +13,50 @@ use clap::Parser;
 struct Args {
     #[arg(short, long)]
     file: String,
+
+    /// Print the matches and instances of every card
+    #[arg(short, long)]
+    report: bool,
+
+    /// Print the originals that result in the most cards
+    #[arg(short, long)]
+    top: Option<usize>,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
-
-    let result = run(&content);
-
-    println!("{}", result)
-}
-
-fn run(content: &str) -> u32 {
-    let cards: Vec<Card> = content.lines().map(|l| l.try_into().unwrap()).collect();
-    let mut num_of_cards = vec![1; cards.len()];
-
-    for (i, card) in cards.iter().enumerate() {
-        let wins = card.wins();
-        for j in i + 1..i + 1 + wins {
-            num_of_cards[j] += num_of_cards[i];
+    file.read_to_string(&mut content)?;
+
+    let cards = scratchcards::parse(&content)?;
+    let cascade = Cascade::new(&cards)?;
+
+    if args.report {
+        for (i, card) in cards.iter().enumerate() {
+            println!(
+                "Card {}: {} matches, {} instances",
+                card.id,
+                card.matches(),
+                cascade.copies[i]
+            );
         }
     }
 
-    num_of_cards.iter().sum()
-}
-
-#[derive(Debug)]
-struct Card {
-    winning: Vec<u32>,
-    have: Vec<u32>,
-}
-
-impl Card {
-    fn wins(&self) -> usize {
-        self.have
-            .iter()
-            .filter(|n| self.winning.contains(n))
-            .count()
-    }
-}
-
-// Parsing
-
-impl TryFrom<&str> for Card {
-    type Error = Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let parts: Vec<&str> = value.split(":").collect();
-        if parts.len() != 2 {
-            return Err(anyhow!(
-                "first split of the card line was not successful: {}",
-                value
-            ));
+    if let Some(n) = args.top {
+        for i in cascade.top_producers(n) {
+            println!(
+                "Card {} results in {} cards",
+                cards[i].id, cascade.produced[i]
+            );
         }
-
-        let number_lists: Vec<&str> = parts[1].split("|").collect();
-        if number_lists.len() != 2 {
-            return Err(anyhow!(
-                "second split of the card line was not successful: {}",
-                parts[1]
-            ));
-        }
-
-        let winning = convert_number_list(number_lists[0]);
-        let have = convert_number_list(number_lists[1]);
-
-        Ok(Card { winning, have })
     }
-}
 
-fn convert_number_list(list: &str) -> Vec<u32> {
-    list.trim()
-        .split(" ")
-        .filter(|n| !n.is_empty())
-        .map(|n| n.parse::<u32>().expect("parts should be all digits"))
-        .collect()
+    println!("{}", cascade.total()?);
+
+    Ok(())
 }
 
 // testing
@@ -101,6 +67,12 @@ mod tests {
 
     use super::*;
 
+    fn run(content: &str) -> Result<u64, Error> {
+        let cards = scratchcards::parse(content)?;
+
+        Cascade::new(&cards)?.total()
+    }
+
     #[test]
     fn test_short() {
         let file = "short_data";
@@ -108,7 +80,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 30)
     }
 
@@ -119,7 +91,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 8063216)
     }
 
@@ -0,0 +1,257 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+
+// Set of small non-negative numbers, one bit each
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bitset {
+    words: Vec<u64>,
+}
+
+impl Bitset {
+    pub fn insert(&mut self, n: usize) {
+        let word = n / u64::BITS as usize;
+        if self.words.len() <= word {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (n % u64::BITS as usize);
+    }
+
+    pub fn contains(&self, n: usize) -> bool {
+        self.words
+            .get(n / u64::BITS as usize)
+            .is_some_and(|word| word & (1 << (n % u64::BITS as usize)) != 0)
+    }
+
+    pub fn intersection_len(&self, other: &Bitset) -> usize {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a & b).count_ones() as usize)
+            .sum()
+    }
+}
+
+impl FromStr for Bitset {
+    type Err = Error;
+
+    fn from_str(list: &str) -> Result<Self, Self::Err> {
+        let mut set = Bitset::default();
+        for n in list.split_ascii_whitespace() {
+            // Bounded so a stray large number cannot blow up the words
+            let n: u16 = n
+                .parse()
+                .map_err(|e| anyhow!("invalid number {}: {}", n, e))?;
+            set.insert(n.into());
+        }
+        Ok(set)
+    }
+}
+
+#[derive(Debug)]
+pub struct Card {
+    pub id: usize,
+    pub winning: Bitset,
+    pub have: Bitset,
+}
+
+impl Card {
+    pub fn matches(&self) -> usize {
+        self.winning.intersection_len(&self.have)
+    }
+
+    pub fn points(&self) -> Result<u64, Error> {
+        match self.matches() {
+            0 => Ok(0),
+            matches => u32::try_from(matches - 1)
+                .ok()
+                .and_then(|shift| 1u64.checked_shl(shift))
+                .ok_or(anyhow!(
+                    "card {} is worth more points than fit in a u64",
+                    self.id
+                )),
+        }
+    }
+}
+
+impl TryFrom<&str> for Card {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (id_part, numbers) = value.split_once(':').ok_or(anyhow!(
+            "first split of the card line was not successful: {}",
+            value
+        ))?;
+
+        let id = id_part
+            .strip_prefix("Card")
+            .and_then(|id| id.trim().parse().ok())
+            .ok_or(anyhow!("invalid card id part: {}", id_part))?;
+
+        let (winning, have) = numbers.split_once('|').ok_or(anyhow!(
+            "second split of the card line was not successful: {}",
+            numbers
+        ))?;
+
+        Ok(Card {
+            id,
+            winning: winning.parse()?,
+            have: have.parse()?,
+        })
+    }
+}
+
+pub fn parse(content: &str) -> Result<Vec<Card>, Error> {
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(Card::try_from)
+        .collect()
+}
+
+// Result of playing every card with all the copies it wins
+#[derive(Debug)]
+pub struct Cascade {
+    // Instances of every card, the original included
+    pub copies: Vec<u64>,
+    // Cards eventually resulting from a single original, itself included
+    pub produced: Vec<u64>,
+}
+
+impl Cascade {
+    pub fn new(cards: &[Card]) -> Result<Self, Error> {
+        let matches: Vec<usize> = cards.iter().map(Card::matches).collect();
+
+        for (i, (card, matches)) in cards.iter().zip(&matches).enumerate() {
+            if card.id != i + 1 {
+                return Err(anyhow!("card {} is listed as card {}", card.id, i + 1));
+            }
+            if i + matches >= cards.len() {
+                return Err(anyhow!(
+                    "card {} wins {} copies past the last card {}",
+                    card.id,
+                    matches,
+                    cards.len()
+                ));
+            }
+        }
+
+        let too_many = || anyhow!("cards win more copies than fit in a u64");
+
+        let mut copies = vec![1u64; cards.len()];
+        for (i, matches) in matches.iter().enumerate() {
+            let instances = copies[i];
+            for copy in &mut copies[i + 1..=i + matches] {
+                *copy = copy.checked_add(instances).ok_or_else(too_many)?;
+            }
+        }
+
+        // Later cards never win earlier ones, so they are finished first
+        let mut produced = vec![1u64; cards.len()];
+        for (i, matches) in matches.iter().enumerate().rev() {
+            produced[i] = produced[i..=i + matches]
+                .iter()
+                .try_fold(0u64, |sum, n| sum.checked_add(*n))
+                .ok_or_else(too_many)?;
+        }
+
+        Ok(Cascade { copies, produced })
+    }
+
+    pub fn total(&self) -> Result<u64, Error> {
+        self.copies
+            .iter()
+            .try_fold(0u64, |sum, n| sum.checked_add(*n))
+            .ok_or(anyhow!("total of the cards overflows"))
+    }
+
+    // Indices of the `n` originals producing the most cards, largest first
+    pub fn top_producers(&self, n: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.produced.len()).collect();
+        order.sort_by(|a, b| self.produced[*b].cmp(&self.produced[*a]));
+        order.truncate(n);
+        order
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const SAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn test_bitset() {
+        let set: Bitset = "1 64 200".parse().unwrap();
+        assert!(set.contains(1) && set.contains(64) && set.contains(200));
+        assert!(!set.contains(2) && !set.contains(1000));
+
+        let other: Bitset = "64 200 3".parse().unwrap();
+        assert_eq!(set.intersection_len(&other), 2);
+
+        assert!("1 65536".parse::<Bitset>().is_err());
+    }
+
+    #[test]
+    fn test_cascade() {
+        let cards = parse(SAMPLE).unwrap();
+        let points: u64 = cards.iter().map(|card| card.points().unwrap()).sum();
+        assert_eq!(points, 13);
+
+        let cascade = Cascade::new(&cards).unwrap();
+        assert_eq!(cascade.copies, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(cascade.produced, vec![15, 7, 4, 2, 1, 1]);
+        assert_eq!(cascade.total().unwrap(), 30);
+        assert_eq!(cascade.top_producers(2), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_past_the_end() {
+        let cards = parse(&SAMPLE.replace("Card 6: 31 18", "Card 6: 74 77")).unwrap();
+        assert!(Cascade::new(&cards).is_err());
+
+        let cards = parse(&SAMPLE.replace("Card 6:", "Card 7:")).unwrap();
+        assert!(Cascade::new(&cards).is_err());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let numbers: String = (1..=64).map(|n| format!("{} ", n)).collect();
+        let card = Card::try_from(format!("Card 1: {}| {}", numbers, numbers).as_str()).unwrap();
+        assert_eq!(card.points().unwrap(), 1 << 63);
+        let card =
+            Card::try_from(format!("Card 1: {}65 | {}65", numbers, numbers).as_str()).unwrap();
+        assert!(card.points().is_err());
+
+        // Every card wins the next two, so the copies grow like the Fibonacci numbers
+        let content: String = (1..=100)
+            .map(|id| match id {
+                1..=98 => format!("Card {}: 1 2 | 1 2\n", id),
+                _ => format!("Card {}: 1 | 2\n", id),
+            })
+            .collect();
+        assert!(Cascade::new(&parse(&content).unwrap()).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            if let Ok(cards) = parse(&content) {
+                for card in &cards {
+                    let _ = card.points();
+                }
+                if let Ok(cascade) = Cascade::new(&cards) {
+                    let _ = cascade.total();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::{anyhow, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Seeds {
+    pub start: i64,
+    pub len: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IngredientMap {
+    pub dest: i64,
+    pub src: i64,
+    pub len: i64,
+}
+
+// The seed ranges and every block of maps, in the order of the almanac
+pub fn parse(content: &str) -> Result<(Vec<Seeds>, Vec<Vec<IngredientMap>>), Error> {
+    let mut lines = content.lines();
+
+    let seeds = parse_seeds(lines.next().ok_or(anyhow!("almanac is empty"))?)?;
+
+    lines.next();
+
+    let mut maps = vec![];
+    while lines.next().is_some() {
+        maps.push(
+            (&mut lines)
+                .take_while(|l| !l.is_empty())
+                .map(IngredientMap::try_from)
+                .collect::<Result<_, _>>()?,
+        );
+    }
+
+    Ok((seeds, maps))
+}
+
+fn parse_seeds(line: &str) -> Result<Vec<Seeds>, Error> {
+    let numbers = line
+        .split_ascii_whitespace()
+        .skip(1)
+        .map(parse_number)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    numbers
+        .chunks(2)
+        .map(|c| match c {
+            [start, len] if start.checked_add(*len).is_some() => Ok(Seeds {
+                start: *start,
+                len: *len,
+            }),
+            [_, _] => Err(anyhow!("seed range is too large: {}", line)),
+            _ => Err(anyhow!(
+                "seeds should come in pairs of start and length: {}",
+                line
+            )),
+        })
+        .collect()
+}
+
+impl TryFrom<&str> for IngredientMap {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let numbers = value
+            .split_ascii_whitespace()
+            .map(parse_number)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match numbers[..] {
+            [dest, src, len]
+                if dest.checked_add(len).is_some() && src.checked_add(len).is_some() =>
+            {
+                Ok(IngredientMap { dest, src, len })
+            }
+            [_, _, _] => Err(anyhow!("map range is too large: {}", value)),
+            _ => Err(anyhow!("map should have the form dest src len: {}", value)),
+        }
+    }
+}
+
+fn parse_number(n: &str) -> Result<i64, Error> {
+    n.parse()
+        .map_err(|e| anyhow!("invalid number {}: {}", n, e))
+        .and_then(|n: i64| match n {
+            0.. => Ok(n),
+            _ => Err(anyhow!("number should not be negative: {}", n)),
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        let (seeds, maps) = parse("seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n").unwrap();
+        assert_eq!(seeds[1], Seeds { start: 55, len: 13 });
+        assert_eq!(
+            maps,
+            vec![vec![IngredientMap {
+                dest: 50,
+                src: 98,
+                len: 2
+            }]]
+        );
+
+        assert!(parse("").is_err());
+        assert!(parse("seeds: 79 14 55").is_err());
+        assert!(parse("seeds: 1 9223372036854775807").is_err());
+        assert!(parse("seeds: 1 2\n\nx map:\n1 2\n").is_err());
+        assert!(parse("seeds: 1 2\n\nx map:\n1 -2 3\n").is_err());
+        assert!(parse("seeds: 1 2\n\nx map:\n1 2 9223372036854775807\n").is_err());
+    }
+}
@@ -2,8 +2,10 @@
 
 extern crate test;
 
-use std::{fs::File, i64, io::Read, str::SplitAsciiWhitespace};
+use std::{fs::File, io::Read};
 
+use anyhow::{anyhow, Error};
+use aoc2023_day5b::almanac::{self, IngredientMap, Seeds};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -13,28 +15,30 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
+    println!("{}", result);
+
+    Ok(())
 }
 
-fn run(content: &str) -> i64 {
-    let (seeds, maps) = parse(content);
+fn run(content: &str) -> Result<i64, Error> {
+    let (seeds, maps) = almanac::parse(content)?;
 
     maps.iter()
         .fold(seeds, apply_maps)
         .into_iter()
         .map(|s| s.start)
         .min()
-        .expect("resulting vector should have at least one element")
+        .ok_or(anyhow!("almanac lists no seeds"))
 }
 fn apply_maps(seeds: Vec<Seeds>, maps: &Vec<IngredientMap>) -> Vec<Seeds> {
     seeds
@@ -88,91 +92,13 @@ fn apply_maps_single(seeds: Seeds, maps: &Vec<IngredientMap>) -> Vec<Seeds> {
     new_seeds
 }
 
-// Parsing
-
-fn parse(content: &str) -> (Vec<Seeds>, Vec<Vec<IngredientMap>>) {
-    let mut lines = content.lines();
-
-    let seeds = parse_seeds(lines.next().expect("input has at least one line"));
-
-    lines.next();
-
-    let mut maps = vec![];
-    while let Some(_) = lines.next() {
-        maps.push(
-            (&mut lines)
-                .take_while(|l| !l.is_empty())
-                .map(|l| l.into())
-                .collect(),
-        );
-    }
-
-    (seeds, maps)
-}
-
-fn parse_seeds(line: &str) -> Vec<Seeds> {
-    let numbers: Vec<i64> = line
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(|n| {
-            n.parse::<i64>()
-                .expect("all parts should consist of digits only")
-        })
-        .collect();
-
-    numbers
-        .chunks(2)
-        .map(|c| {
-            if c.len() != 2 {
-                panic!("something wrong with parsing the seed line")
-            }
-
-            Seeds {
-                start: c[0],
-                len: c[1],
-            }
-        })
-        .collect()
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Seeds {
-    start: i64,
-    len: i64,
-}
-
-#[derive(Debug)]
-struct IngredientMap {
-    dest: i64,
-    src: i64,
-    len: i64,
-}
-
-impl From<&str> for IngredientMap {
-    fn from(value: &str) -> Self {
-        let mut parts = value.split_ascii_whitespace();
-
-        IngredientMap {
-            dest: parse_part(&mut parts),
-            src: parse_part(&mut parts),
-            len: parse_part(&mut parts),
-        }
-    }
-}
-
-fn parse_part(parts: &mut SplitAsciiWhitespace) -> i64 {
-    parts
-        .next()
-        .expect("element should exist")
-        .parse()
-        .expect("element should consist of digits only")
-}
-
 // testing
 
 #[cfg(test)]
 mod tests {
     use ::test::Bencher;
+    use aoc2023_day5b_prime::walk;
+    use proptest::{collection::vec, prelude::*};
 
     use super::*;
 
@@ -183,7 +109,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 46)
     }
 
@@ -194,7 +120,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 79874951)
     }
 
@@ -208,6 +134,83 @@ mod tests {
         b.iter(|| run(&content));
     }
 
+    // Valid almanacs of small seed ranges and blocks of non-overlapping maps, the maps of
+    // a block listed in random order
+    fn almanac() -> impl Strategy<Value = String> {
+        let seeds = vec((0i64..100, 1i64..20), 1..4);
+        let block = vec((0i64..10, 1i64..30, 0i64..300), 1..5)
+            .prop_map(|maps| {
+                let mut src = 0;
+                maps.into_iter()
+                    .map(|(gap, len, dest)| {
+                        src += gap;
+                        let line = format!("{} {} {}", dest, src, len);
+                        src += len;
+                        line
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .prop_flat_map(|lines| Just(lines).prop_shuffle());
+
+        (seeds, vec(block, 1..5)).prop_map(|(seeds, blocks)| {
+            let seeds: Vec<String> = seeds
+                .iter()
+                .map(|(start, len)| format!("{} {}", start, len))
+                .collect();
+            let blocks: Vec<String> = blocks
+                .iter()
+                .enumerate()
+                .map(|(i, lines)| format!("{}-to-{} map:\n{}\n", i, i + 1, lines.join("\n")))
+                .collect();
+
+            format!("seeds: {}\n\n{}", seeds.join(" "), blocks.join("\n"))
+        })
+    }
+
+    // Maps every seed on its own
+    fn brute_force(content: &str) -> i64 {
+        let (seeds, maps) = almanac::parse(content).unwrap();
+
+        seeds
+            .iter()
+            .flat_map(|s| s.start..s.start + s.len)
+            .map(|mut seed| {
+                for block in &maps {
+                    if let Some(m) = block.iter().find(|m| m.src <= seed && seed < m.src + m.len) {
+                        seed += m.dest - m.src;
+                    }
+                }
+                seed
+            })
+            .min()
+            .unwrap()
+    }
+
+    fn prime(content: &str) -> i64 {
+        let (seeds, maps) = almanac::parse(content).unwrap();
+        let maps = walk::fill_map_gaps(maps);
+
+        seeds
+            .iter()
+            .map(|s| walk::process_seeds(&maps, s))
+            .min()
+            .unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn test_against_references(content in almanac()) {
+            let result = run(&content).unwrap();
+            prop_assert_eq!(result, brute_force(&content));
+            prop_assert_eq!(result, prime(&content));
+        }
+
+        #[test]
+        fn test_parse_any(content in "\\PC*") {
+            let _ = almanac::parse(&content);
+        }
+    }
+
     // Debugging:
 
     #[test]
@@ -2,8 +2,11 @@
 
 extern crate test;
 
-use std::{fs::File, i64, io::Read, str::SplitAsciiWhitespace};
+use std::{fs::File, io::Read};
 
+use anyhow::{anyhow, Error};
+use aoc2023_day5b::almanac;
+use aoc2023_day5b_prime::walk;
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -13,23 +16,25 @@ struct Args {
     file: String,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
+    file.read_to_string(&mut content)?;
 
-    let result = run(&content);
+    let result = run(&content)?;
 
-    println!("{}", result)
+    println!("{}", result);
+
+    Ok(())
 }
 
-fn run(content: &str) -> i64 {
-    let (seeds, maps) = parse(content);
+fn run(content: &str) -> Result<i64, Error> {
+    let (seeds, maps) = almanac::parse(content)?;
 
-    let maps = fill_map_gaps(maps);
+    let maps = walk::fill_map_gaps(maps);
 
     for m in &maps {
         println!("{:?}", m);
@@ -37,160 +42,9 @@ fn run(content: &str) -> i64 {
 
     seeds
         .iter()
-        .map(|s| process_seeds(&maps, s))
-        .min()
-        .expect("at least one set of seeds in input")
-}
-
-fn process_seeds(maps: &Vec<Vec<IngredientMap>>, seeds: &Seeds) -> i64 {
-    let mut terminals = vec![];
-
-    let mut start = seeds.start;
-    while start < seeds.start + seeds.len {
-        let (terminal, processed) = walk(maps, start, seeds.start + seeds.len - start);
-
-        terminals.push(terminal);
-        start += processed;
-    }
-
-    terminals
-        .into_iter()
+        .map(|s| walk::process_seeds(&maps, s))
         .min()
-        .expect("at least one terminal expected")
-}
-
-fn walk(maps: &[Vec<IngredientMap>], start: i64, len: i64) -> (i64, i64) {
-    if maps.len() == 0 {
-        return (start, len);
-    }
-
-    let map = maps
-        .get(0)
-        .expect("length is greater than 0")
-        .iter()
-        .filter(|m| m.src <= start && start < m.src + m.len)
-        .next()
-        .expect("at least one map should fulfill this map");
-
-    let len = len.min(map.len - (start - map.src));
-    let start = start + map.dest - map.src;
-
-    walk(&maps[1..], start, len)
-}
-
-// Fill Gaps
-
-fn fill_map_gaps(maps: Vec<Vec<IngredientMap>>) -> Vec<Vec<IngredientMap>> {
-    maps.into_iter().map(fill_map_gaps_step).collect()
-}
-
-fn fill_map_gaps_step(og_maps: Vec<IngredientMap>) -> Vec<IngredientMap> {
-    let mut og_maps = og_maps;
-    og_maps.sort_by_key(|m| m.src);
-
-    let mut maps = vec![];
-    let mut last_not_mapped = 0;
-
-    for map in og_maps {
-        if map.src > last_not_mapped {
-            maps.push(IngredientMap {
-                dest: last_not_mapped,
-                src: last_not_mapped,
-                len: map.src - last_not_mapped,
-            })
-        }
-        last_not_mapped = map.src + map.len;
-
-        maps.push(map);
-    }
-
-    maps.push(IngredientMap {
-        dest: last_not_mapped,
-        src: last_not_mapped,
-        len: i64::max_value() - last_not_mapped,
-    });
-
-    maps
-}
-
-// Parsing
-
-fn parse(content: &str) -> (Vec<Seeds>, Vec<Vec<IngredientMap>>) {
-    let mut lines = content.lines();
-
-    let seeds = parse_seeds(lines.next().expect("input has at least one line"));
-
-    lines.next();
-
-    let mut maps = vec![];
-    while let Some(_) = lines.next() {
-        maps.push(
-            (&mut lines)
-                .take_while(|l| !l.is_empty())
-                .map(|l| l.into())
-                .collect(),
-        );
-    }
-
-    (seeds, maps)
-}
-
-fn parse_seeds(line: &str) -> Vec<Seeds> {
-    let numbers: Vec<i64> = line
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(|n| {
-            n.parse::<i64>()
-                .expect("all parts should consist of digits only")
-        })
-        .collect();
-
-    numbers
-        .chunks(2)
-        .map(|c| {
-            if c.len() != 2 {
-                panic!("something wrong with parsing the seed line")
-            }
-
-            Seeds {
-                start: c[0],
-                len: c[1],
-            }
-        })
-        .collect()
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Seeds {
-    start: i64,
-    len: i64,
-}
-
-#[derive(Debug)]
-struct IngredientMap {
-    dest: i64,
-    src: i64,
-    len: i64,
-}
-
-impl From<&str> for IngredientMap {
-    fn from(value: &str) -> Self {
-        let mut parts = value.split_ascii_whitespace();
-
-        IngredientMap {
-            dest: parse_part(&mut parts),
-            src: parse_part(&mut parts),
-            len: parse_part(&mut parts),
-        }
-    }
-}
-
-fn parse_part(parts: &mut SplitAsciiWhitespace) -> i64 {
-    parts
-        .next()
-        .expect("element should exist")
-        .parse()
-        .expect("element should consist of digits only")
+        .ok_or(anyhow!("almanac lists no seeds"))
 }
 
 // testing
@@ -208,7 +62,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 46)
     }
 
@@ -219,7 +73,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 79874951)
     }
 
@@ -0,0 +1,72 @@
+use aoc2023_day5b::almanac::{IngredientMap, Seeds};
+
+pub fn process_seeds(maps: &Vec<Vec<IngredientMap>>, seeds: &Seeds) -> i64 {
+    let mut terminals = vec![];
+
+    let mut start = seeds.start;
+    while start < seeds.start + seeds.len {
+        let (terminal, processed) = walk(maps, start, seeds.start + seeds.len - start);
+
+        terminals.push(terminal);
+        start += processed;
+    }
+
+    terminals
+        .into_iter()
+        .min()
+        .expect("at least one terminal expected")
+}
+
+fn walk(maps: &[Vec<IngredientMap>], start: i64, len: i64) -> (i64, i64) {
+    if maps.len() == 0 {
+        return (start, len);
+    }
+
+    let map = maps
+        .get(0)
+        .expect("length is greater than 0")
+        .iter()
+        .filter(|m| m.src <= start && start < m.src + m.len)
+        .next()
+        .expect("at least one map should fulfill this map");
+
+    let len = len.min(map.len - (start - map.src));
+    let start = start + map.dest - map.src;
+
+    walk(&maps[1..], start, len)
+}
+
+// Fill Gaps
+
+pub fn fill_map_gaps(maps: Vec<Vec<IngredientMap>>) -> Vec<Vec<IngredientMap>> {
+    maps.into_iter().map(fill_map_gaps_step).collect()
+}
+
+fn fill_map_gaps_step(og_maps: Vec<IngredientMap>) -> Vec<IngredientMap> {
+    let mut og_maps = og_maps;
+    og_maps.sort_by_key(|m| m.src);
+
+    let mut maps = vec![];
+    let mut last_not_mapped = 0;
+
+    for map in og_maps {
+        if map.src > last_not_mapped {
+            maps.push(IngredientMap {
+                dest: last_not_mapped,
+                src: last_not_mapped,
+                len: map.src - last_not_mapped,
+            })
+        }
+        last_not_mapped = map.src + map.len;
+
+        maps.push(map);
+    }
+
+    maps.push(IngredientMap {
+        dest: last_not_mapped,
+        src: last_not_mapped,
+        len: i64::max_value() - last_not_mapped,
+    });
+
+    maps
+}
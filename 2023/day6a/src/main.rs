@@ -2,14 +2,10 @@
 
 extern crate test;
 
-use std::{
-    fs::File,
-    io::{BufRead, Read},
-    ops::IndexMut,
-    str::{Lines, SplitAsciiWhitespace},
-    u32, u64,
-};
+use std::{fs::File, io::Read};
 
+use anyhow::Error;
+use aoc2023_day6b::race;
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -17,64 +13,40 @@ use clap::Parser;
 struct Args {
     #[arg(short, long)]
     file: String,
+
+    /// Print the winning hold times of every race
+    #[arg(short, long)]
+    intervals: bool,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
-
-    let result = run(&content);
-
-    println!("{}", result)
-}
-
-fn run(content: &str) -> u32 {
-    let rounds = parse(content);
-
-    rounds.iter().map(number_of_ways_to_win).product()
-}
-
-fn number_of_ways_to_win(round: &Round) -> u32 {
-    (0..round.time).filter(|t| will_win(t, round)).count() as u32
-}
-
-fn will_win(hold_time: &u32, round: &Round) -> bool {
-    let speed = hold_time;
-    let travel_time = round.time - hold_time;
-    let our_dist = travel_time * speed;
-
-    our_dist > round.dist
-}
-
-// Parsing
-
-fn parse(content: &str) -> Vec<Round> {
-    let mut lines = content.lines();
-
-    let times = parse_line(lines.next().expect("input has two lines"));
-    let dists = parse_line(lines.next().expect("input has two lines"));
-
-    times
-        .zip(dists)
-        .map(|(time, dist)| Round { time, dist })
-        .collect()
-}
+    file.read_to_string(&mut content)?;
+
+    let races = race::parse(&content)?;
+
+    if args.intervals {
+        for (i, race) in races.iter().enumerate() {
+            match race.winning() {
+                Some(range) => println!(
+                    "race {}: hold {} to {} ms, {} ways",
+                    i + 1,
+                    range.start(),
+                    range.end(),
+                    race.ways()
+                ),
+                None => println!("race {}: the record cannot be beaten", i + 1),
+            }
+        }
+    }
 
-fn parse_line<'a>(line: &'a str) -> Box<dyn Iterator<Item = u32> + 'a> {
-    Box::new(line.split_ascii_whitespace().skip(1).map(|p| {
-        p.parse::<u32>()
-            .expect("parts should consist of digits only")
-    }))
-}
+    println!("{}", race::product(&races)?);
 
-#[derive(Debug, Clone, Copy)]
-struct Round {
-    time: u32,
-    dist: u32,
+    Ok(())
 }
 
 // testing
@@ -92,7 +64,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = race::product(&race::parse(&content).unwrap()).unwrap();
         assert_eq!(result, 288)
     }
 
@@ -103,7 +75,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = race::product(&race::parse(&content).unwrap()).unwrap();
         assert_eq!(result, 781200)
     }
 
@@ -114,6 +86,6 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content));
+        b.iter(|| race::product(&race::parse(&content).unwrap()));
     }
 }
@@ -2,8 +2,10 @@
 
 extern crate test;
 
-use std::{f64, fs::File, io::Read};
+use std::{fs::File, io::Read};
 
+use anyhow::Error;
+use aoc2023_day6b::race;
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -11,61 +13,40 @@ use clap::Parser;
 struct Args {
     #[arg(short, long)]
     file: String,
+
+    /// Print the winning hold times of every race
+    #[arg(short, long)]
+    intervals: bool,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
-
-    let result = run(&content);
-
-    println!("{}", result)
-}
-
-fn run(content: &str) -> u64 {
-    let round = parse(content);
-
-    number_of_ways_to_win(&round)
-}
-
-fn number_of_ways_to_win(round: &Round) -> u64 {
-    let root = ((round.time.pow(2) - 4 * round.dist) as f64).sqrt();
-    let zero_point_l = (round.time as f64 - root) / 2.;
-    let zero_point_r = (round.time as f64 + root) / 2.;
-
-    let l = zero_point_l.ceil() as u64;
-    let r = zero_point_r.floor() as u64;
-
-    r + 1 - l
-}
-
-// Parsing
-
-fn parse(content: &str) -> Round {
-    let mut lines = content.lines();
-
-    let time = parse_line(lines.next().expect("input has two lines"));
-    let dist = parse_line(lines.next().expect("input has two lines"));
-
-    Round { time, dist }
-}
+    file.read_to_string(&mut content)?;
+
+    let races = vec![race::parse_kerned(&content)?];
+
+    if args.intervals {
+        for (i, race) in races.iter().enumerate() {
+            match race.winning() {
+                Some(range) => println!(
+                    "race {}: hold {} to {} ms, {} ways",
+                    i + 1,
+                    range.start(),
+                    range.end(),
+                    race.ways()
+                ),
+                None => println!("race {}: the record cannot be beaten", i + 1),
+            }
+        }
+    }
 
-fn parse_line(line: &str) -> u64 {
-    line.split_ascii_whitespace()
-        .skip(1)
-        .collect::<String>()
-        .parse()
-        .expect("should consist of digits only")
-}
+    println!("{}", race::product(&races)?);
 
-#[derive(Debug, Clone, Copy)]
-struct Round {
-    time: u64,
-    dist: u64,
+    Ok(())
 }
 
 // testing
@@ -83,7 +64,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = race::product(&[race::parse_kerned(&content).unwrap()]).unwrap();
         assert_eq!(result, 71503)
     }
 
@@ -94,7 +75,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = race::product(&[race::parse_kerned(&content).unwrap()]).unwrap();
         assert_eq!(result, 49240091)
     }
 
@@ -105,6 +86,6 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content));
+        b.iter(|| race::product(&[race::parse_kerned(&content).unwrap()]));
     }
 }
@@ -0,0 +1,215 @@
+use std::ops::RangeInclusive;
+
+use anyhow::{anyhow, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u128,
+    pub record: u128,
+}
+
+impl Race {
+    // Holding for h ms travels h * (time - h) mm, a tie with the record does not win
+    pub fn wins(&self, hold: u128) -> bool {
+        if hold > self.time {
+            return false;
+        }
+
+        match hold.checked_mul(self.time - hold) {
+            Some(distance) => distance > self.record,
+            // Farther than any record could be
+            None => true,
+        }
+    }
+
+    // Hold times beating the record. The distance is symmetric around time / 2, so only
+    // the lower bound is searched for: it lies next to the smaller root of
+    // h^2 - time * h + record, which is (time - sqrt(time^2 - 4 * record)) / 2.
+    pub fn winning(&self) -> Option<RangeInclusive<u128>> {
+        let middle = self.time / 2;
+        if !self.wins(middle) {
+            return None;
+        }
+
+        let discriminant = self
+            .time
+            .checked_mul(self.time)
+            .and_then(|square| square.checked_sub(self.record.checked_mul(4)?));
+
+        let mut low = match discriminant {
+            Some(discriminant) => (self.time - isqrt(discriminant)) / 2,
+            // Too large to square, search the boundary instead
+            None => {
+                let (mut low, mut high) = (0, middle);
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    if self.wins(mid) {
+                        high = mid;
+                    } else {
+                        low = mid + 1;
+                    }
+                }
+                low
+            }
+        };
+
+        // The estimate is at most one off since the square root is rounded down
+        while !self.wins(low) {
+            low += 1;
+        }
+        while low > 0 && self.wins(low - 1) {
+            low -= 1;
+        }
+
+        Some(low..=self.time - low)
+    }
+
+    pub fn ways(&self) -> u128 {
+        self.winning()
+            .map(|range| range.end() - range.start() + 1)
+            .unwrap_or(0)
+    }
+}
+
+// Largest r with r * r <= n, by Newton's method starting above the root
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    let bits = u128::BITS - n.leading_zeros();
+    let mut x = 1u128 << bits.div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+// Product of the number of ways to win every race
+pub fn product(races: &[Race]) -> Result<u128, Error> {
+    races.iter().try_fold(1u128, |product, race| {
+        product
+            .checked_mul(race.ways())
+            .ok_or(anyhow!("product of the ways to win overflows"))
+    })
+}
+
+// Parsing
+
+// A line of times and a line of records, one race per column
+pub fn parse(content: &str) -> Result<Vec<Race>, Error> {
+    let mut lines = content.lines();
+
+    let times = parse_line(lines.next().ok_or(anyhow!("input has two lines"))?)?;
+    let records = parse_line(lines.next().ok_or(anyhow!("input has two lines"))?)?;
+    if times.len() != records.len() {
+        return Err(anyhow!("every race should have a time and a record"));
+    }
+
+    Ok(times
+        .into_iter()
+        .zip(records)
+        .map(|(time, record)| Race { time, record })
+        .collect())
+}
+
+// The kerning is bad, all numbers on a line form a single race
+pub fn parse_kerned(content: &str) -> Result<Race, Error> {
+    let mut lines = content.lines();
+
+    let time = parse_kerned_line(lines.next().ok_or(anyhow!("input has two lines"))?)?;
+    let record = parse_kerned_line(lines.next().ok_or(anyhow!("input has two lines"))?)?;
+
+    Ok(Race { time, record })
+}
+
+fn parse_line(line: &str) -> Result<Vec<u128>, Error> {
+    line.split_ascii_whitespace()
+        .skip(1)
+        .map(|p| {
+            p.parse()
+                .map_err(|e| anyhow!("invalid number {}: {}", p, e))
+        })
+        .collect()
+}
+
+fn parse_kerned_line(line: &str) -> Result<u128, Error> {
+    let digits = line.split_ascii_whitespace().skip(1).collect::<String>();
+    digits
+        .parse()
+        .map_err(|e| anyhow!("invalid number {}: {}", digits, e))
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..10_000u128 {
+            let r = isqrt(n);
+            assert!(r * r <= n && n < (r + 1) * (r + 1), "isqrt({}) = {}", n, r);
+        }
+
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(isqrt((1 << 100) - 1), (1 << 50) - 1);
+    }
+
+    #[test]
+    fn test_ties() {
+        let race = Race {
+            time: 30,
+            record: 200,
+        };
+        assert_eq!(race.winning(), Some(11..=19));
+        assert_eq!(race.ways(), 9);
+
+        let tie = Race { time: 4, record: 4 };
+        assert_eq!(tie.winning(), None);
+
+        let lost = Race {
+            time: 3,
+            record: 10,
+        };
+        assert_eq!(lost.ways(), 0);
+    }
+
+    #[test]
+    fn test_large() {
+        let time = 10u128.pow(30);
+        let race = Race { time, record: 0 };
+        assert_eq!(race.winning(), Some(1..=time - 1));
+
+        let record = 10u128.pow(5) * (time - 10u128.pow(5));
+        let race = Race { time, record };
+        assert_eq!(
+            race.winning(),
+            Some(10u128.pow(5) + 1..=time - 10u128.pow(5) - 1)
+        );
+
+        for time in 0..200 {
+            for record in [0, time, time * time / 5, time * time / 4] {
+                let race = Race { time, record };
+                let brute_force = (0..=time).filter(|hold| race.wins(*hold)).count();
+                assert_eq!(race.ways(), brute_force as u128);
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            if let Ok(races) = parse(&content) {
+                let _ = product(&races);
+            }
+            if let Ok(race) = parse_kerned(&content) {
+                let _ = race.ways();
+            }
+        }
+    }
+}
@@ -2,15 +2,10 @@
 
 extern crate test;
 
-use std::{
-    cmp::Ordering::Equal,
-    fs::File,
-    io::{BufRead, Read},
-    str::{FromStr, Lines, SplitAsciiWhitespace},
-    u32, u64,
-};
-
-use anyhow::{anyhow, Error};
+use std::{fs::File, io::Read};
+
+use anyhow::Error;
+use aoc2023_day7b::cards::{self, Hand, Rules};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -18,6 +13,11 @@ use clap::Parser;
 struct Args {
     #[arg(short, long)]
     file: String,
+    /// standard, jokers-wild or the path to a rules file
+    #[arg(short, long, default_value = "standard")]
+    rules: String,
+    #[arg(short, long)]
+    explain: bool,
 }
 
 fn main() {
@@ -28,158 +28,37 @@ fn main() {
     let mut content = String::new();
     file.read_to_string(&mut content).unwrap();
 
-    let result = run(&content);
-
-    println!("{}", result)
-}
-
-fn run(content: &str) -> u64 {
-    let mut hands = parse(content);
-    hands.sort();
-
-    hands
-        .iter()
-        .enumerate()
-        .map(|(i, h)| (i as u64 + 1) * h.bid)
-        .sum()
-}
-
-fn parse(content: &str) -> Vec<Hand> {
-    content
-        .lines()
-        .map(Hand::from_str)
-        .collect::<Result<_, _>>()
-        .expect("error while parsing input")
-}
-
-#[derive(Debug)]
-struct Hand {
-    cards: Vec<Card>,
-    bid: u64,
-}
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum HandType {
-    HighestCard,
-    Pair,
-    TwoPair,
-    Triplet,
-    FullHouse,
-    Four,
-    Five,
-}
-
-impl Eq for Hand {}
-impl PartialEq for Hand {
-    fn eq(&self, other: &Self) -> bool {
-        self.compare(other) == Equal
-    }
-}
-
-impl Ord for Hand {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.compare(other)
-    }
-}
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.compare(other))
-    }
-}
-
-impl Hand {
-    fn compare(&self, other: &Self) -> std::cmp::Ordering {
-        let self_hand_type = self.hand_type();
-        let other_hand_type = other.hand_type();
-
-        if self_hand_type == other_hand_type {
-            self.cards.iter().cmp(&other.cards)
-        } else {
-            self_hand_type.cmp(&other_hand_type)
+    let rules = Rules::load(&args.rules).unwrap();
+
+    if args.explain {
+        let hands = cards::rank(parse(&content, &rules).unwrap());
+        for (i, hand) in hands.iter().enumerate() {
+            println!(
+                "{} {}: {}, rank {}",
+                String::from_iter(&hand.cards),
+                hand.bid,
+                rules.type_name(hand.hand_type),
+                i + 1
+            );
         }
     }
 
-    fn hand_type(&self) -> HandType {
-        let max_eq = self.matches().max().expect("should be non-empty");
-
-        match max_eq {
-            5 => HandType::Five,
-            4 => HandType::Four,
-            3 => {
-                let min_eq = self.matches().min().expect("should be non-empty");
-
-                if min_eq == 2 {
-                    HandType::FullHouse
-                } else {
-                    HandType::Triplet
-                }
-            }
-            2 => {
-                if self.matches().filter(|m| *m == 2).count() == 4 {
-                    HandType::TwoPair
-                } else {
-                    HandType::Pair
-                }
-            }
-            _ => HandType::HighestCard,
-        }
-    }
+    let result = run(&content, &rules).unwrap();
 
-    fn matches<'a>(&'a self) -> Box<dyn Iterator<Item = usize> + 'a> {
-        Box::new(
-            self.cards
-                .iter()
-                .map(|c| self.cards.iter().filter(|o| &c == o).count()),
-        )
-    }
+    println!("{}", result)
 }
 
-impl FromStr for Hand {
-    type Err = Error;
+fn run(content: &str, rules: &Rules) -> Result<u64, Error> {
+    let hands = cards::rank(parse(content, rules)?);
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split_ascii_whitespace();
-
-        let hand_part = parts.next().ok_or(anyhow!("line should have two parts"))?;
-        let cards: Vec<Card> = hand_part
-            .chars()
-            .take(5)
-            .map(|c| Card::try_from(c))
-            .collect::<Result<_, _>>()
-            .expect("problen while parsing hand");
-
-        let bid_part = parts.next().ok_or(anyhow!("line should have two parts"))?;
-        let bid = bid_part.parse()?;
-
-        Ok(Hand { cards, bid })
-    }
+    Ok(cards::winnings(&hands))
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum Card {
-    Number(u8),
-    Jack,
-    Queen,
-    King,
-    Ass,
-}
-
-impl TryFrom<char> for Card {
-    type Error = Error;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        Ok(match value {
-            'A' => Self::Ass,
-            'K' => Self::King,
-            'Q' => Self::Queen,
-            'J' => Self::Jack,
-            'T' => Self::Number(10),
-            c => {
-                let num = String::from(c).parse()?;
-                Self::Number(num)
-            }
-        })
-    }
+fn parse(content: &str, rules: &Rules) -> Result<Vec<Hand>, Error> {
+    content
+        .lines()
+        .map(|line| Hand::parse(line, rules))
+        .collect()
 }
 
 // testing
@@ -196,7 +75,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content, &Rules::standard()).unwrap();
         assert_eq!(result, 6440)
     }
 
@@ -207,7 +86,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content, &Rules::standard()).unwrap();
         assert_eq!(result, 248396258)
     }
 
@@ -218,6 +97,6 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content));
+        b.iter(|| run(&content, &Rules::standard()));
     }
 }
@@ -0,0 +1,246 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+
+// Everything that differs between variants of Camel Cards
+#[derive(Debug, Clone)]
+pub struct Rules {
+    // Card labels from weakest to strongest
+    pub order: Vec<char>,
+    pub wildcards: Vec<char>,
+    pub hand_size: usize,
+    // Hand types from weakest to strongest with the group sizes a hand needs to have,
+    // largest group first
+    pub hand_types: Vec<(String, Vec<usize>)>,
+}
+
+impl Rules {
+    pub fn standard() -> Self {
+        Rules {
+            order: "23456789TJQKA".chars().collect(),
+            wildcards: vec![],
+            hand_size: 5,
+            hand_types: [
+                ("High card", vec![1, 1, 1, 1, 1]),
+                ("One pair", vec![2, 1, 1, 1]),
+                ("Two pair", vec![2, 2, 1]),
+                ("Three of a kind", vec![3, 1, 1]),
+                ("Full house", vec![3, 2]),
+                ("Four of a kind", vec![4, 1]),
+                ("Five of a kind", vec![5]),
+            ]
+            .into_iter()
+            .map(|(name, groups)| (name.to_string(), groups))
+            .collect(),
+        }
+    }
+
+    pub fn jokers_wild() -> Self {
+        Rules {
+            order: "J23456789TQKA".chars().collect(),
+            wildcards: vec!['J'],
+            ..Rules::standard()
+        }
+    }
+
+    // Built in rule set by name, or a custom one read from a file
+    pub fn load(name: &str) -> Result<Self, Error> {
+        match name {
+            "standard" => Ok(Rules::standard()),
+            "jokers-wild" => Ok(Rules::jokers_wild()),
+            path => std::fs::read_to_string(path)?.parse(),
+        }
+    }
+
+    // Wildcards always join the largest group of regular cards
+    pub fn classify(&self, cards: &[char]) -> Option<usize> {
+        let mut groups: Vec<usize> = vec![];
+        let mut labels: Vec<char> = vec![];
+        for card in cards.iter().filter(|card| !self.wildcards.contains(card)) {
+            match labels.iter().position(|label| label == card) {
+                Some(i) => groups[i] += 1,
+                None => {
+                    labels.push(*card);
+                    groups.push(1);
+                }
+            }
+        }
+        groups.sort_by(|a, b| b.cmp(a));
+
+        let wildcards = cards.len() - groups.iter().sum::<usize>();
+        match groups.first_mut() {
+            Some(largest) => *largest += wildcards,
+            None => groups.push(wildcards),
+        }
+
+        self.hand_types.iter().rposition(|(_, required)| {
+            required.len() <= groups.len()
+                && required
+                    .iter()
+                    .zip(&groups)
+                    .all(|(required, group)| group >= required)
+        })
+    }
+
+    pub fn strength(&self, card: char) -> Option<usize> {
+        self.order.iter().position(|label| *label == card)
+    }
+
+    pub fn type_name(&self, hand_type: Option<usize>) -> &str {
+        hand_type
+            .map(|i| self.hand_types[i].0.as_str())
+            .unwrap_or("No type")
+    }
+}
+
+// Lines of `key = value`, where `order`, `wild` and `size` configure the cards and every
+// other key names a hand type with its comma separated group sizes, weakest type first
+impl FromStr for Rules {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = Rules {
+            order: vec![],
+            wildcards: vec![],
+            hand_size: 5,
+            hand_types: vec![],
+        };
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(anyhow!("rule should have the form key = value: {}", line))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            match key {
+                "order" => rules.order = value.chars().collect(),
+                "wild" => rules.wildcards = value.chars().collect(),
+                "size" => rules.hand_size = value.parse()?,
+                name => {
+                    let groups = value
+                        .split(',')
+                        .map(|group| group.trim().parse())
+                        .collect::<Result<_, _>>()?;
+                    rules.hand_types.push((name.to_string(), groups));
+                }
+            }
+        }
+
+        if rules.order.is_empty() {
+            return Err(anyhow!("rules do not define a card order"));
+        }
+        if let Some(card) = rules.wildcards.iter().find(|c| !rules.order.contains(c)) {
+            return Err(anyhow!("wildcard {} is not part of the card order", card));
+        }
+
+        Ok(rules)
+    }
+}
+
+#[derive(Debug)]
+pub struct Hand {
+    pub cards: Vec<char>,
+    pub bid: u64,
+    pub hand_type: Option<usize>,
+    strengths: Vec<usize>,
+}
+
+impl Hand {
+    pub fn parse(s: &str, rules: &Rules) -> Result<Self, Error> {
+        let mut parts = s.split_ascii_whitespace();
+
+        let hand_part = parts.next().ok_or(anyhow!("line should have two parts"))?;
+        let cards: Vec<char> = hand_part.chars().collect();
+        if cards.len() != rules.hand_size {
+            return Err(anyhow!(
+                "hand {} should have {} cards",
+                hand_part,
+                rules.hand_size
+            ));
+        }
+
+        let strengths = cards
+            .iter()
+            .map(|card| {
+                rules
+                    .strength(*card)
+                    .ok_or(anyhow!("unknown card {} in hand {}", card, hand_part))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let bid_part = parts.next().ok_or(anyhow!("line should have two parts"))?;
+        let bid = bid_part.parse()?;
+
+        let hand_type = rules.classify(&cards);
+
+        Ok(Hand {
+            cards,
+            bid,
+            hand_type,
+            strengths,
+        })
+    }
+
+    // Hand types first, ties are broken card by card from the left
+    pub fn key(&self) -> (Option<usize>, &[usize]) {
+        (self.hand_type, &self.strengths)
+    }
+}
+
+// Hands from weakest to strongest
+pub fn rank(mut hands: Vec<Hand>) -> Vec<Hand> {
+    hands.sort_by(|a, b| a.key().cmp(&b.key()));
+    hands
+}
+
+pub fn winnings(hands: &[Hand]) -> u64 {
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, h)| (i as u64 + 1) * h.bid)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hand_type(rules: &Rules, cards: &str) -> String {
+        let cards: Vec<char> = cards.chars().collect();
+        rules.type_name(rules.classify(&cards)).to_string()
+    }
+
+    #[test]
+    fn test_standard() {
+        let rules = Rules::standard();
+
+        assert_eq!(hand_type(&rules, "AAAAA"), "Five of a kind");
+        assert_eq!(hand_type(&rules, "AAAAJ"), "Four of a kind");
+        assert_eq!(hand_type(&rules, "AAJJJ"), "Full house");
+        assert_eq!(hand_type(&rules, "KTJJT"), "Two pair");
+        assert_eq!(hand_type(&rules, "23456"), "High card");
+    }
+
+    #[test]
+    fn test_custom() {
+        let rules: Rules = "
+            # three card poker without jacks
+            order = 23456789TQKA
+            size = 3
+            Nothing = 1
+            Pair = 2
+            Triple = 3
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(hand_type(&rules, "2Q2"), "Pair");
+        assert_eq!(hand_type(&rules, "AAA"), "Triple");
+        assert!(Hand::parse("2J2 1", &rules).is_err());
+        assert!(Hand::parse("2222 1", &rules).is_err());
+    }
+}
@@ -2,16 +2,10 @@
 
 extern crate test;
 
-use std::{
-    cmp::Ordering::Equal,
-    collections::HashSet,
-    fs::File,
-    io::{BufRead, Read},
-    str::{FromStr, Lines, SplitAsciiWhitespace},
-    u32, u64,
-};
-
-use anyhow::{anyhow, Error};
+use std::{fs::File, io::Read};
+
+use anyhow::Error;
+use aoc2023_day7b::cards::{self, Hand, Rules};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -19,6 +13,11 @@ use clap::Parser;
 struct Args {
     #[arg(short, long)]
     file: String,
+    /// standard, jokers-wild or the path to a rules file
+    #[arg(short, long, default_value = "jokers-wild")]
+    rules: String,
+    #[arg(short, long)]
+    explain: bool,
 }
 
 fn main() {
@@ -29,163 +28,44 @@ fn main() {
     let mut content = String::new();
     file.read_to_string(&mut content).unwrap();
 
-    let result = run(&content);
+    let rules = Rules::load(&args.rules).unwrap();
+
+    if args.explain {
+        let hands = cards::rank(parse(&content, &rules).unwrap());
+        for (i, hand) in hands.iter().enumerate() {
+            println!(
+                "{} {}: {}, rank {}",
+                String::from_iter(&hand.cards),
+                hand.bid,
+                rules.type_name(hand.hand_type),
+                i + 1
+            );
+        }
+    }
+
+    let result = run(&content, &rules).unwrap();
 
     println!("{}", result)
 }
 
-fn run(content: &str) -> u64 {
-    let mut hands = parse(content);
-    hands.sort();
+fn run(content: &str, rules: &Rules) -> Result<u64, Error> {
+    let hands = cards::rank(parse(content, rules)?);
 
-    hands
-        .iter()
-        .enumerate()
-        .map(|(i, h)| (i as u64 + 1) * h.bid)
-        .sum()
+    Ok(cards::winnings(&hands))
 }
 
-fn parse(content: &str) -> Vec<Hand> {
+fn parse(content: &str, rules: &Rules) -> Result<Vec<Hand>, Error> {
     content
         .lines()
-        .map(Hand::from_str)
-        .collect::<Result<_, _>>()
-        .expect("error while parsing input")
-}
-
-#[derive(Debug)]
-struct Hand {
-    cards: Vec<Card>,
-    bid: u64,
-}
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum HandType {
-    HighestCard,
-    Pair,
-    TwoPair,
-    Triplet,
-    FullHouse,
-    Four,
-    Five,
-}
-
-impl Eq for Hand {}
-impl PartialEq for Hand {
-    fn eq(&self, other: &Self) -> bool {
-        self.compare(other) == Equal
-    }
-}
-
-impl Ord for Hand {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.compare(other)
-    }
-}
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.compare(other))
-    }
-}
-
-impl Hand {
-    fn compare(&self, other: &Self) -> std::cmp::Ordering {
-        let self_hand_type = self.hand_type();
-        let other_hand_type = other.hand_type();
-
-        if self_hand_type == other_hand_type {
-            self.cards.iter().cmp(&other.cards)
-        } else {
-            self_hand_type.cmp(&other_hand_type)
-        }
-    }
-
-    fn hand_type(&self) -> HandType {
-        let same: HashSet<(usize, &Card)> = self
-            .cards
-            .iter()
-            .filter(|c| **c != Card::Jack)
-            .map(|c| (self.cards.iter().filter(|o| c == *o).count(), c))
-            .collect();
-        let mut same: Vec<(usize, &Card)> = same.into_iter().collect();
-        same.sort_by_key(|t| t.0);
-        same.reverse();
-
-        let num_jokers = self.cards.iter().filter(|c| *c == &Card::Jack).count();
-        match same.get(0).map(|t| t.0 + num_jokers).unwrap_or(5) {
-            5 => HandType::Five,
-            4 => HandType::Four,
-            3 => {
-                if same.get(1).expect("should always exist").0 == 2 {
-                    HandType::FullHouse
-                } else {
-                    HandType::Triplet
-                }
-            }
-            2 => {
-                if same.get(1).expect("should always exist").0 == 2 {
-                    HandType::TwoPair
-                } else {
-                    HandType::Pair
-                }
-            }
-            _ => HandType::HighestCard,
-        }
-    }
-}
-
-impl FromStr for Hand {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split_ascii_whitespace();
-
-        let hand_part = parts.next().ok_or(anyhow!("line should have two parts"))?;
-        let cards: Vec<Card> = hand_part
-            .chars()
-            .take(5)
-            .map(|c| Card::try_from(c))
-            .collect::<Result<_, _>>()
-            .expect("problen while parsing hand");
-
-        let bid_part = parts.next().ok_or(anyhow!("line should have two parts"))?;
-        let bid = bid_part.parse()?;
-
-        Ok(Hand { cards, bid })
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-enum Card {
-    Jack,
-    Number(u8),
-    Queen,
-    King,
-    Ass,
-}
-
-impl TryFrom<char> for Card {
-    type Error = Error;
-
-    fn try_from(value: char) -> Result<Self, Self::Error> {
-        Ok(match value {
-            'A' => Self::Ass,
-            'K' => Self::King,
-            'Q' => Self::Queen,
-            'J' => Self::Jack,
-            'T' => Self::Number(10),
-            c => {
-                let num = String::from(c).parse()?;
-                Self::Number(num)
-            }
-        })
-    }
+        .map(|line| Hand::parse(line, rules))
+        .collect()
 }
 
 // testing
 #[cfg(test)]
 mod tests {
     use ::test::Bencher;
+    use proptest::{collection::vec, prelude::*, sample::select};
 
     use super::*;
 
@@ -196,7 +76,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content, &Rules::jokers_wild()).unwrap();
         assert_eq!(result, 5905)
     }
 
@@ -207,7 +87,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content, &Rules::jokers_wild()).unwrap();
         assert_eq!(result, 246436046)
     }
 
@@ -218,175 +98,107 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content));
+        b.iter(|| run(&content, &Rules::jokers_wild()));
+    }
+
+    fn hand_type(cards: &str) -> String {
+        let rules = Rules::jokers_wild();
+        let cards: Vec<char> = cards.chars().collect();
+
+        rules.type_name(rules.classify(&cards)).to_string()
     }
 
     #[test]
     fn test_five() {
-        let h = Hand {
-            cards: vec![Card::Ass, Card::Ass, Card::Ass, Card::Ass, Card::Ass],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Five);
-        let h = Hand {
-            cards: vec![Card::Ass, Card::Ass, Card::Ass, Card::Ass, Card::Jack],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Five);
-        let h = Hand {
-            cards: vec![Card::Ass, Card::Ass, Card::Ass, Card::Jack, Card::Jack],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Five);
-        let h = Hand {
-            cards: vec![Card::Ass, Card::Ass, Card::Jack, Card::Jack, Card::Jack],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Five);
-        let h = Hand {
-            cards: vec![Card::Ass, Card::Jack, Card::Jack, Card::Jack, Card::Jack],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Five);
+        assert_eq!(hand_type("AAAAA"), "Five of a kind");
+        assert_eq!(hand_type("AAAAJ"), "Five of a kind");
+        assert_eq!(hand_type("AAAJJ"), "Five of a kind");
+        assert_eq!(hand_type("AAJJJ"), "Five of a kind");
+        assert_eq!(hand_type("AJJJJ"), "Five of a kind");
     }
 
     #[test]
     fn test_four() {
-        let h = Hand {
-            cards: vec![Card::Ass, Card::Ass, Card::Ass, Card::Ass, Card::Number(2)],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Four);
-        let h = Hand {
-            cards: vec![Card::Ass, Card::Ass, Card::Ass, Card::Jack, Card::Number(2)],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Four);
-        let h = Hand {
-            cards: vec![
-                Card::Ass,
-                Card::Ass,
-                Card::Jack,
-                Card::Jack,
-                Card::Number(2),
-            ],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Four);
-        let h = Hand {
-            cards: vec![
-                Card::Ass,
-                Card::Jack,
-                Card::Jack,
-                Card::Jack,
-                Card::Number(2),
-            ],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Four);
+        assert_eq!(hand_type("AAAA2"), "Four of a kind");
+        assert_eq!(hand_type("AAAJ2"), "Four of a kind");
+        assert_eq!(hand_type("AAJJ2"), "Four of a kind");
+        assert_eq!(hand_type("AJJJ2"), "Four of a kind");
     }
 
     #[test]
     fn test_triple() {
-        let h = Hand {
-            cards: vec![
-                Card::Ass,
-                Card::Ass,
-                Card::Ass,
-                Card::Number(2),
-                Card::Number(3),
-            ],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Triplet);
-        let h = Hand {
-            cards: vec![
-                Card::Ass,
-                Card::Ass,
-                Card::Jack,
-                Card::Number(2),
-                Card::Number(3),
-            ],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Triplet);
-        let h = Hand {
-            cards: vec![
-                Card::Ass,
-                Card::Jack,
-                Card::Jack,
-                Card::Number(2),
-                Card::Number(3),
-            ],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Triplet);
+        assert_eq!(hand_type("AAA23"), "Three of a kind");
+        assert_eq!(hand_type("AAJ23"), "Three of a kind");
+        assert_eq!(hand_type("AJJ23"), "Three of a kind");
     }
 
     #[test]
     fn test_full_house() {
-        let h = Hand {
-            cards: vec![
-                Card::Ass,
-                Card::Ass,
-                Card::Ass,
-                Card::Number(2),
-                Card::Number(2),
-            ],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::FullHouse);
-        let h = Hand {
-            cards: vec![
-                Card::Ass,
-                Card::Ass,
-                Card::Jack,
-                Card::Number(2),
-                Card::Number(2),
-            ],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::FullHouse);
+        assert_eq!(hand_type("AAA22"), "Full house");
+        assert_eq!(hand_type("AAJ22"), "Full house");
     }
 
     #[test]
     fn test_two_pair() {
-        let h = Hand {
-            cards: vec![
-                Card::Ass,
-                Card::Ass,
-                Card::King,
-                Card::King,
-                Card::Number(2),
-            ],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::TwoPair);
+        assert_eq!(hand_type("AAKK2"), "Two pair");
     }
 
     #[test]
     fn test_pair() {
-        let h = Hand {
-            cards: vec![
-                Card::Ass,
-                Card::Ass,
-                Card::King,
-                Card::Queen,
-                Card::Number(2),
-            ],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Pair);
-        let h = Hand {
-            cards: vec![
-                Card::Ass,
-                Card::Jack,
-                Card::King,
-                Card::Queen,
-                Card::Number(2),
-            ],
-            bid: 0,
-        };
-        assert_eq!(h.hand_type(), HandType::Pair);
+        assert_eq!(hand_type("AAKQ2"), "One pair");
+        assert_eq!(hand_type("AJKQ2"), "One pair");
+    }
+
+    fn hand() -> impl Strategy<Value = String> {
+        let labels: Vec<char> = "23456789TJQKA".chars().collect();
+        vec(select(labels), 5).prop_map(|cards| cards.into_iter().collect())
+    }
+
+    // Tries every label a joker could stand for under the standard rules
+    fn best_substitution(cards: &[char]) -> Option<usize> {
+        let standard = Rules::standard();
+
+        match cards.iter().position(|card| *card == 'J') {
+            None => standard.classify(cards),
+            Some(i) => "23456789TQKA"
+                .chars()
+                .map(|label| {
+                    let mut cards = cards.to_vec();
+                    cards[i] = label;
+                    best_substitution(&cards)
+                })
+                .max()
+                .unwrap(),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_against_substitution(cards in hand()) {
+            let cards: Vec<char> = cards.chars().collect();
+            prop_assert_eq!(Rules::jokers_wild().classify(&cards), best_substitution(&cards));
+        }
+
+        #[test]
+        fn test_ranking(hands in vec((hand(), 1u64..1000), 1..20)) {
+            let content = hands
+                .iter()
+                .map(|(cards, bid)| format!("{} {}", cards, bid))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let rules = Rules::jokers_wild();
+            let ranked = cards::rank(parse(&content, &rules).unwrap());
+
+            for pair in ranked.windows(2) {
+                prop_assert!(pair[0].key() <= pair[1].key());
+            }
+        }
+
+        #[test]
+        fn test_parse_any(line in "\\PC*") {
+            let _ = Hand::parse(&line, &Rules::standard());
+            let _ = Hand::parse(&line, &Rules::jokers_wild());
+            let _ = line.parse::<Rules>();
+        }
     }
 }
@@ -2,9 +2,10 @@
 
 extern crate test;
 
-use std::{collections::HashMap, fs::File, io::Read, str::FromStr};
+use std::{fs::File, io::Read};
 
-use anyhow::{anyhow, Error};
+use anyhow::Error;
+use aoc2023_day8b::network::Network;
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -12,109 +13,57 @@ use clap::Parser;
 struct Args {
     #[arg(short, long)]
     file: String,
-}
-
-fn main() {
-    let args = Args::parse();
-    let file = args.file;
-
-    let mut file = File::open(file).unwrap();
-    let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
-
-    let result = run(&content);
-
-    println!("{}", result)
-}
-
-fn run(content: &str) -> u64 {
-    let (instructions, edges) = parse(content);
 
-    let mut count = 0;
-    let mut curr_node = String::from("AAA");
-    let end_node = String::from("ZZZ");
-    while curr_node != end_node {
-        let edges = edges
-            .get(&curr_node)
-            .expect("curr_node should always exist in the mapping");
+    #[arg(long, default_value = "AAA")]
+    from: String,
 
-        curr_node = match instructions[count % instructions.len()] {
-            Instruction::Left => edges.left.clone(),
-            Instruction::Right => edges.right.clone(),
-        };
-
-        count += 1;
-    }
+    #[arg(long, default_value = "ZZZ")]
+    to: String,
 
-    count as u64
+    /// Print the node reached after this many steps instead
+    #[arg(short, long)]
+    steps: Option<u64>,
 }
 
-fn parse(content: &str) -> (Vec<Instruction>, HashMap<String, Edges>) {
-    let mut lines = content.lines();
-
-    let instruction_line = lines.next().expect("input has at least one line");
-    let instructions = instruction_line.chars().map(Instruction::from).collect();
-
-    lines.next();
-
-    let edges = lines
-        .map(|l| {
-            let mut parts = l.split(" = ");
-
-            let start_part = parts.next().expect("should have two parts").to_string();
-            let edges = parts
-                .next()
-                .expect("should have two parts")
-                .parse()
-                .expect("edges should be parsable");
-
-            (start_part, edges)
-        })
-        .collect();
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let file = args.file;
 
-    (instructions, edges)
-}
+    let mut file = File::open(file)?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
 
-#[derive(Debug)]
-enum Instruction {
-    Left,
-    Right,
-}
+    let network: Network = content.parse()?;
+    let from = network.id(&args.from)?;
 
-impl From<char> for Instruction {
-    fn from(value: char) -> Self {
-        match value {
-            'L' => Instruction::Left,
-            'R' => Instruction::Right,
-            _ => panic!("Unknown instruction: '{}'", value),
-        }
+    match args.steps {
+        Some(steps) => println!("{}", network.name(network.position(from, steps))),
+        None => match network.target(network.id(&args.to)?).distance(from) {
+            Some(distance) => println!("{}", distance),
+            None => println!("{} is never reached from {}", args.to, args.from),
+        },
     }
-}
 
-#[derive(Debug)]
-struct Edges {
-    left: String,
-    right: String,
-}
-
-impl FromStr for Edges {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Edges {
-            left: s[1..4].to_string(),
-            right: s[6..9].to_string(),
-        })
-    }
+    Ok(())
 }
 
 // testing
 #[cfg(test)]
 mod tests {
     use ::test::Bencher;
+    use anyhow::anyhow;
 
     use super::*;
 
+    fn run(content: &str) -> Result<u64, Error> {
+        let network: Network = content.parse()?;
+        let target = network.target(network.id("ZZZ")?);
+
+        target
+            .distance(network.id("AAA")?)
+            .ok_or(anyhow!("ZZZ is never reached from AAA"))
+    }
+
     #[test]
     fn test_short() {
         let file = "short_data";
@@ -122,7 +71,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 6)
     }
 
@@ -133,7 +82,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = run(&content).unwrap();
         assert_eq!(result, 18727)
     }
 
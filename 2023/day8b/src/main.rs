@@ -2,11 +2,12 @@
 
 extern crate test;
 
-use std::{collections::HashMap, fs::File, io::Read, str::FromStr, u32, u64, usize};
+use std::{fs::File, io::Read};
 
-use anyhow::{anyhow, Error};
+use aoc2023_day8b::network::Network;
 use clap::Parser;
-use num::{integer::lcm, Integer};
+use iter_tools::Itertools;
+use num::{integer::ExtendedGcd, Integer};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -29,101 +30,176 @@ fn main() {
 }
 
 fn run(content: &str) -> usize {
-    let (instructions, edges) = parse(content);
-
-    println!("{}", instructions.len());
+    let network: Network = content.parse().expect("network should be parsable");
+
+    let walks = starts(&network)
+        .map(|start| Walk::new(&network, start))
+        .collect_vec();
+
+    let result = align(&walks)
+        .or_else(|| simulate(&network, SIMULATION_LIMIT))
+        .expect("ghosts never stand on Z nodes at the same time");
+
+    if let Some(shortcut) = lcm_shortcut(&walks) {
+        if shortcut != result {
+            eprintln!(
+                "warning: the LCM shortcut would give {} instead of {}",
+                shortcut, result
+            );
+        }
+    }
 
-    edges
-        .iter()
-        .map(|(node, _)| node)
-        .filter(|node| node.ends_with('A'))
-        .map(|node| num_of_iterations(node, &instructions, &edges))
-        .reduce(|a, b| a.lcm(&b))
-        .expect("")
-        * instructions.len()
+    result
 }
 
-fn num_of_iterations(
-    node: &String,
-    instructions: &Vec<Instruction>,
-    edges: &HashMap<String, Edges>,
-) -> usize {
-    let mut count = 0;
-    let mut node = node;
-
-    while !node.ends_with('Z') {
-        node = instructions.iter().fold(node, |node, i| {
-            let edge = edges.get(node).expect("node should exist");
-
-            match i {
-                Instruction::Left => &edge.left,
-                Instruction::Right => &edge.right,
-            }
-        });
+const SIMULATION_LIMIT: usize = 100_000_000;
+const MAX_RESIDUES: usize = 10_000;
 
-        count += 1;
-    }
+fn starts(network: &Network) -> impl Iterator<Item = usize> + '_ {
+    network
+        .nodes()
+        .filter(|node| network.name(*node).ends_with('A'))
+}
 
-    count
+fn is_target(network: &Network, node: usize) -> bool {
+    network.name(node).ends_with('Z')
 }
 
-fn parse(content: &str) -> (Vec<Instruction>, HashMap<String, Edges>) {
-    let mut lines = content.lines();
+// Walk of a single ghost through the (node, instruction index) states. After `tail`
+// steps it repeats with period `cycle`, `targets` are all times before the second
+// pass through the cycle at which it stands on a Z node.
+#[derive(Debug)]
+struct Walk {
+    tail: usize,
+    cycle: usize,
+    targets: Vec<usize>,
+}
 
-    let instruction_line = lines.next().expect("input has at least one line");
-    let instructions = instruction_line.chars().map(Instruction::from).collect();
+impl Walk {
+    fn new(network: &Network, start: usize) -> Self {
+        let len = network.cycle_len();
+        let mut seen = vec![usize::MAX; network.len() * len];
+
+        let mut targets = vec![];
+        let mut node = start;
+        let mut time = 0;
+
+        loop {
+            let state = node * len + time % len;
+            if seen[state] != usize::MAX {
+                let tail = seen[state];
+                return Walk {
+                    tail,
+                    cycle: time - tail,
+                    targets,
+                };
+            }
+            seen[state] = time;
 
-    lines.next();
+            if is_target(network, node) {
+                targets.push(time);
+            }
 
-    let edges = lines
-        .map(|l| {
-            let mut parts = l.split(" = ");
+            node = network.step(node, time);
+            time += 1;
+        }
+    }
 
-            let start_part = parts.next().expect("should have two parts").to_string();
-            let edges = parts
-                .next()
-                .expect("should have two parts")
-                .parse()
-                .expect("edges should be parsable");
+    fn is_target(&self, time: usize) -> bool {
+        let time = if time < self.tail {
+            time
+        } else {
+            self.tail + (time - self.tail) % self.cycle
+        };
 
-            (start_part, edges)
-        })
-        .collect();
+        self.targets.binary_search(&time).is_ok()
+    }
 
-    (instructions, edges)
+    // Residues modulo the cycle length of the times in the cycle on a Z node
+    fn residues(&self) -> Vec<i128> {
+        self.targets
+            .iter()
+            .filter(|time| **time >= self.tail)
+            .map(|time| (*time % self.cycle) as i128)
+            .collect()
+    }
 }
 
-#[derive(Debug)]
-enum Instruction {
-    Left,
-    Right,
-}
+// First time at which all ghosts stand on a Z node, either while some are still in their
+// tail or by combining the residues of all cycles with the chinese remainder theorem
+fn align(walks: &[Walk]) -> Option<usize> {
+    let settled = walks.iter().map(|walk| walk.tail).max().unwrap_or(0);
 
-impl From<char> for Instruction {
-    fn from(value: char) -> Self {
-        match value {
-            'L' => Instruction::Left,
-            'R' => Instruction::Right,
-            _ => panic!("Unknown instruction: '{}'", value),
+    let early = walks
+        .iter()
+        .flat_map(|walk| walk.targets.iter().copied())
+        .filter(|time| *time < settled)
+        .filter(|time| walks.iter().all(|walk| walk.is_target(*time)))
+        .min();
+    if early.is_some() {
+        return early;
+    }
+
+    let mut residues = vec![(0, 1)];
+    for walk in walks {
+        residues = residues
+            .into_iter()
+            .cartesian_product(walk.residues())
+            .filter_map(|((r, m), residue)| crt(r, m, residue, walk.cycle as i128))
+            .unique()
+            .collect();
+
+        if residues.len() > MAX_RESIDUES {
+            return None;
         }
     }
+
+    residues
+        .into_iter()
+        .map(|(r, m)| {
+            let settled = settled as i128;
+            let time = settled + (r - settled).rem_euclid(m);
+            time as usize
+        })
+        .min()
 }
 
-#[derive(Debug)]
-struct Edges {
-    left: String,
-    right: String,
+// Combines x = a mod m and x = b mod n into a single congruence, if there is a solution
+fn crt(a: i128, m: i128, b: i128, n: i128) -> Option<(i128, i128)> {
+    let ExtendedGcd { gcd, x, .. } = m.extended_gcd(&n);
+    if (b - a) % gcd != 0 {
+        return None;
+    }
+
+    let lcm = m / gcd * n;
+    let k = ((b - a) / gcd % (n / gcd)) * (x % (n / gcd)) % (n / gcd);
+    Some(((a + m * k).rem_euclid(lcm), lcm))
 }
 
-impl FromStr for Edges {
-    type Err = Error;
+fn simulate(network: &Network, limit: usize) -> Option<usize> {
+    let mut nodes = starts(network).collect_vec();
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Edges {
-            left: s[1..4].to_string(),
-            right: s[6..9].to_string(),
-        })
+    for time in 0..limit {
+        if nodes.iter().all(|node| is_target(network, *node)) {
+            return Some(time);
+        }
+
+        for node in nodes.iter_mut() {
+            *node = network.step(*node, time);
+        }
     }
+
+    None
+}
+
+// The puzzle's intended answer, the LCM of the first arrivals, only holds if every ghost
+// reaches its only Z node exactly at the end of each cycle
+fn lcm_shortcut(walks: &[Walk]) -> Option<usize> {
+    walks
+        .iter()
+        .map(|walk| walk.targets.first().copied())
+        .reduce(|a, b| Some(a?.lcm(&b?)))
+        .flatten()
 }
 
 // testing
@@ -144,6 +220,20 @@ mod tests {
         assert_eq!(result, 6)
     }
 
+    #[test]
+    fn test_extra() {
+        let file = "extra_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        let result = run(&content);
+        assert_eq!(result, 5);
+
+        let network: Network = content.parse().unwrap();
+        assert_eq!(simulate(&network, 100), Some(5));
+    }
+
     #[test]
     fn test_long() {
         let file = "long_data";
@@ -152,7 +242,7 @@ mod tests {
         file.read_to_string(&mut content).unwrap();
 
         let result = run(&content);
-        assert_eq!(result, 18727)
+        assert_eq!(result, 18024643846273)
     }
 
     #[bench]
@@ -0,0 +1,257 @@
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::{anyhow, Error};
+
+// Nodes interned to dense ids with the instructions compiled to edge indices. `jumps[k]`
+// holds for every node the node reached after 2^k full passes through the instructions.
+#[derive(Debug)]
+pub struct Network {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    next: Vec<[usize; 2]>,
+    instructions: Vec<usize>,
+    jumps: Vec<Vec<usize>>,
+}
+
+impl Network {
+    pub fn id(&self, name: &str) -> Result<usize, Error> {
+        self.ids
+            .get(name)
+            .copied()
+            .ok_or(anyhow!("unknown node: {}", name))
+    }
+
+    pub fn name(&self, node: usize) -> &str {
+        &self.names[node]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn cycle_len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = usize> + '_ {
+        0..self.names.len()
+    }
+
+    // Node reached from `node` by following the instruction at `time`
+    pub fn step(&self, node: usize, time: usize) -> usize {
+        self.next[node][self.instructions[time % self.instructions.len()]]
+    }
+
+    // Node reached after `steps` steps starting with the first instruction
+    pub fn position(&self, mut node: usize, steps: u64) -> usize {
+        let len = self.instructions.len() as u64;
+        let cycles = steps / len;
+
+        for (k, jump) in self.jumps.iter().enumerate() {
+            if cycles & (1 << k) != 0 {
+                node = jump[node];
+            }
+        }
+
+        for time in 0..(steps % len) as usize {
+            node = self.step(node, time);
+        }
+
+        node
+    }
+
+    // Prepares distance queries towards a single node
+    pub fn target(&self, target: usize) -> Target<'_> {
+        let first = self
+            .nodes()
+            .map(|node| {
+                let mut node = node;
+                for time in 0..self.instructions.len() {
+                    if node == target {
+                        return Some(time);
+                    }
+                    node = self.step(node, time);
+                }
+                None
+            })
+            .collect::<Vec<_>>();
+
+        let mut reach = vec![first.iter().map(Option::is_some).collect::<Vec<_>>()];
+        for k in 1..self.jumps.len() {
+            let previous = &reach[k - 1];
+            let level = self
+                .nodes()
+                .map(|node| previous[node] || previous[self.jumps[k - 1][node]])
+                .collect();
+            reach.push(level);
+        }
+
+        Target {
+            network: self,
+            first,
+            reach,
+        }
+    }
+}
+
+// `first` is the offset at which a pass starting at a node visits the target, and
+// `reach[k]` whether the target is visited during the next 2^k passes
+pub struct Target<'a> {
+    network: &'a Network,
+    first: Vec<Option<usize>>,
+    reach: Vec<Vec<bool>>,
+}
+
+impl Target<'_> {
+    // Number of steps until the target is visited for the first time, in logarithmic time
+    pub fn distance(&self, mut node: usize) -> Option<u64> {
+        let levels = self.reach.len();
+        if !self.reach[levels - 1][node] {
+            return None;
+        }
+
+        let mut cycles = 0u64;
+        for k in (0..levels).rev() {
+            if !self.reach[k][node] {
+                node = self.network.jumps[k][node];
+                cycles += 1 << k;
+            }
+        }
+
+        let offset = self.first[node]? as u64;
+        Some(cycles * self.network.cycle_len() as u64 + offset)
+    }
+}
+
+impl FromStr for Network {
+    type Err = Error;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let mut lines = content.lines();
+
+        let instruction_line = lines.next().ok_or(anyhow!("input is empty"))?;
+        let instructions = instruction_line
+            .chars()
+            .map(|c| match c {
+                'L' => Ok(0),
+                'R' => Ok(1),
+                _ => Err(anyhow!("unknown instruction: '{}'", c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if instructions.is_empty() {
+            return Err(anyhow!("no instructions"));
+        }
+
+        let edges = lines
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (node, edges) = line
+                    .split_once(" = ")
+                    .ok_or(anyhow!("node should have edges: {}", line))?;
+                let (left, right) = edges
+                    .strip_prefix('(')
+                    .and_then(|edges| edges.strip_suffix(')'))
+                    .and_then(|edges| edges.split_once(", "))
+                    .ok_or(anyhow!("edges should have the form (L, R): {}", edges))?;
+
+                Ok((node, left, right))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let names: Vec<String> = edges.iter().map(|(node, _, _)| node.to_string()).collect();
+        let ids: HashMap<String, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), i))
+            .collect();
+
+        let id = |name: &str| {
+            ids.get(name)
+                .copied()
+                .ok_or(anyhow!("unknown node: {}", name))
+        };
+        let next = edges
+            .iter()
+            .map(|(_, left, right)| Ok([id(left)?, id(right)?]))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let mut network = Network {
+            names,
+            ids,
+            next,
+            instructions,
+            jumps: vec![],
+        };
+
+        // One level per bit of a u64 number of passes, so `position` can take any count
+        let cycle = network
+            .nodes()
+            .map(|node| (0..network.cycle_len()).fold(node, |node, time| network.step(node, time)))
+            .collect::<Vec<_>>();
+
+        let levels = u64::BITS as usize;
+        let mut jumps = vec![cycle];
+        for k in 1..levels {
+            let previous = &jumps[k - 1];
+            let level = previous.iter().map(|node| previous[*node]).collect();
+            jumps.push(level);
+        }
+        network.jumps = jumps;
+
+        Ok(network)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_queries() {
+        let network: Network = "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)"
+            .parse()
+            .unwrap();
+
+        let aaa = network.id("AAA").unwrap();
+        let bbb = network.id("BBB").unwrap();
+        let zzz = network.id("ZZZ").unwrap();
+
+        assert_eq!(network.target(zzz).distance(aaa), Some(6));
+        assert_eq!(network.target(bbb).distance(aaa), Some(1));
+        assert_eq!(network.target(aaa).distance(zzz), None);
+
+        assert_eq!(network.position(aaa, 5), bbb);
+        assert_eq!(network.position(aaa, 6), zzz);
+        assert_eq!(network.position(aaa, 1 << 40), zzz);
+    }
+
+    #[test]
+    fn test_positions_on_a_cycle() {
+        let network: Network = "L\n\nAAA = (BBB, AAA)\nBBB = (CCC, BBB)\nCCC = (AAA, CCC)"
+            .parse()
+            .unwrap();
+
+        let nodes = ["AAA", "BBB", "CCC"].map(|name| network.id(name).unwrap());
+
+        for steps in [0, 1, 2, 3, 8, 16, 1 << 40, u64::MAX] {
+            assert_eq!(
+                network.position(nodes[0], steps),
+                nodes[(steps % 3) as usize]
+            );
+        }
+        assert_eq!(network.target(nodes[2]).distance(nodes[0]), Some(2));
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*") {
+            let _ = content.parse::<Network>();
+        }
+    }
+}
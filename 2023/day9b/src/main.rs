@@ -2,71 +2,51 @@
 
 extern crate test;
 
-use std::{collections::VecDeque, fs::File, io::Read, str::FromStr};
+use std::{fs::File, io::Read};
 
+use anyhow::Error;
+use aoc2023_day9b::sequence::{self, Polynomial};
 use clap::Parser;
-use iter_tools::Itertools;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[arg(short, long)]
     file: String,
+
+    /// Steps to extrapolate past the last value, negative ones go before the first
+    #[arg(short, long, default_value_t = -1, allow_negative_numbers = true)]
+    steps: i64,
+
+    /// Print the degree and the extrapolated value of every history
+    #[arg(short, long)]
+    explain: bool,
 }
 
-fn main() {
+fn main() -> Result<(), Error> {
     let args = Args::parse();
     let file = args.file;
 
-    let mut file = File::open(file).unwrap();
+    let mut file = File::open(file)?;
     let mut content = String::new();
-    file.read_to_string(&mut content).unwrap();
-
-    let result = run(&content);
-
-    println!("{}", result)
-}
-
-fn run(content: &str) -> i64 {
-    let histrories = parse(content);
-
-    histrories.into_iter().map(predict).sum()
-}
-
-fn predict(history: Vec<i64>) -> i64 {
-    let differences = compute_differences(history);
-
-    differences.into_iter().fold(0, |acc, difference| {
-        difference
-            .get(0)
-            .expect("differences should always have at least one element")
-            - acc
-    })
-}
-
-fn compute_differences(history: Vec<i64>) -> Vec<Vec<i64>> {
-    let mut res = VecDeque::from([history.clone()]);
-    let mut curr = history;
-
-    while !curr.iter().all(|e| *e == 0) {
-        curr = curr.iter().tuple_windows().map(|(a, b)| b - a).collect();
-
-        res.push_front(curr.clone());
+    file.read_to_string(&mut content)?;
+
+    if args.explain {
+        for history in sequence::parse(&content)? {
+            let polynomial = Polynomial::fit(&history)?;
+            println!(
+                "degree {}: {}",
+                polynomial.degree(),
+                polynomial.extrapolate(args.steps)?
+            );
+        }
     }
 
-    res.into()
-}
+    let result = sequence::run(&content, args.steps)?;
+
+    println!("{}", result);
 
-fn parse(content: &str) -> Vec<Vec<i64>> {
-    content
-        .lines()
-        .map(|l| {
-            l.split_ascii_whitespace()
-                .map(i64::from_str)
-                .collect::<Result<_, _>>()
-                .expect("should be able to parse all numbers")
-        })
-        .collect()
+    Ok(())
 }
 
 // testing
@@ -83,7 +63,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = sequence::run(&content, -1).unwrap();
         assert_eq!(result, 2)
     }
 
@@ -94,7 +74,7 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        let result = run(&content);
+        let result = sequence::run(&content, -1).unwrap();
         assert_eq!(result, 1118)
     }
 
@@ -105,6 +85,6 @@ mod tests {
         let mut content = String::new();
         file.read_to_string(&mut content).unwrap();
 
-        b.iter(|| run(&content));
+        b.iter(|| sequence::run(&content, -1));
     }
 }
@@ -0,0 +1,163 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error};
+
+// Minimal degree polynomial through a sequence of samples, kept in Newton form: the
+// coefficients are the leading entries of the difference pyramid, so the value at x is
+// the sum of coefficient k times binomial(x, k), with x counted from the first sample
+#[derive(Debug, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<i128>,
+    samples: usize,
+}
+
+impl Polynomial {
+    pub fn fit(samples: &[i64]) -> Result<Self, Error> {
+        if samples.is_empty() {
+            return Err(anyhow!("cannot fit an empty sequence"));
+        }
+
+        let mut coefficients = vec![];
+        let mut row: Vec<i128> = samples.iter().map(|sample| *sample as i128).collect();
+
+        while row.iter().any(|value| *value != 0) {
+            // The last entry of a row is only followed by an empty row, which proves nothing
+            if row.len() == 1 {
+                return Err(anyhow!(
+                    "differences of {:?} never reach all zeros",
+                    samples
+                ));
+            }
+
+            coefficients.push(row[0]);
+            row = row
+                .windows(2)
+                .map(|pair| {
+                    pair[1]
+                        .checked_sub(pair[0])
+                        .ok_or(anyhow!("difference overflows in {:?}", samples))
+                })
+                .collect::<Result<_, _>>()?;
+        }
+
+        Ok(Polynomial {
+            coefficients,
+            samples: samples.len(),
+        })
+    }
+
+    // The zero polynomial is reported as degree 0 as well
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    // Value at position `x`, where the samples are at 0 up to their count
+    pub fn at(&self, x: i128) -> Result<i128, Error> {
+        let overflow = || anyhow!("value at {} overflows", x);
+
+        let mut value: i128 = 0;
+        let mut binomial: i128 = 1;
+        for (k, coefficient) in self.coefficients.iter().enumerate() {
+            if k > 0 {
+                // binomial(x, k) = binomial(x, k - 1) * (x - k + 1) / k, which is exact
+                let factor = x.checked_sub(k as i128 - 1).ok_or_else(overflow)?;
+                binomial = binomial.checked_mul(factor).ok_or_else(overflow)? / k as i128;
+            }
+
+            let term = coefficient.checked_mul(binomial).ok_or_else(overflow)?;
+            value = value.checked_add(term).ok_or_else(overflow)?;
+        }
+
+        Ok(value)
+    }
+
+    // Value `steps` places after the last sample, or before the first one if negative
+    pub fn extrapolate(&self, steps: i64) -> Result<i128, Error> {
+        if steps >= 0 {
+            self.at(self.samples as i128 - 1 + steps as i128)
+        } else {
+            self.at(steps as i128)
+        }
+    }
+}
+
+// Sum of the values every history extrapolates to
+pub fn run(content: &str, steps: i64) -> Result<i128, Error> {
+    let histories = parse(content)?;
+
+    histories.iter().try_fold(0i128, |sum, history| {
+        let value = Polynomial::fit(history)?.extrapolate(steps)?;
+        sum.checked_add(value)
+            .ok_or(anyhow!("sum of predictions overflows"))
+    })
+}
+
+// Parsing
+
+// One history per line, as whitespace separated values
+pub fn parse(content: &str) -> Result<Vec<Vec<i64>>, Error> {
+    content
+        .lines()
+        .map(|l| {
+            l.split_ascii_whitespace()
+                .map(|n| i64::from_str(n).map_err(|e| anyhow!("invalid value {}: {}", n, e)))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_degree() {
+        let linear = Polynomial::fit(&[0, 3, 6, 9, 12, 15]).unwrap();
+        assert_eq!(linear.degree(), 1);
+
+        let quadratic = Polynomial::fit(&[1, 3, 6, 10, 15, 21]).unwrap();
+        assert_eq!(quadratic.degree(), 2);
+
+        let cubic = Polynomial::fit(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(cubic.degree(), 3);
+
+        let zero = Polynomial::fit(&[0, 0]).unwrap();
+        assert_eq!(zero.degree(), 0);
+        assert_eq!(zero.extrapolate(5).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        let cubic = Polynomial::fit(&[10, 13, 16, 21, 30, 45]).unwrap();
+        assert_eq!(cubic.extrapolate(1).unwrap(), 68);
+        assert_eq!(cubic.extrapolate(-1).unwrap(), 5);
+        assert_eq!(cubic.extrapolate(0).unwrap(), 45);
+
+        let squares = Polynomial::fit(&[1, 4, 9, 16]).unwrap();
+        assert_eq!(squares.extrapolate(7).unwrap(), 121);
+        assert_eq!(squares.extrapolate(-3).unwrap(), 4);
+        assert_eq!(
+            squares.at(1 << 40).unwrap(),
+            ((1 << 40) + 1) * ((1 << 40) + 1)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(Polynomial::fit(&[]).is_err());
+        assert!(Polynomial::fit(&[1]).is_err());
+        assert!(Polynomial::fit(&[1, 2, 4]).is_err());
+
+        let squares = Polynomial::fit(&[1, 4, 9, 16]).unwrap();
+        assert!(squares.at(1 << 100).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_any(content in "[\\PC\n]*", steps in any::<i64>()) {
+            let _ = run(&content, steps);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc2023_day4b::scratchcards::{self, Cascade};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(cards) = scratchcards::parse(content) {
+            let _ = Cascade::new(&cards);
+        }
+    }
+});
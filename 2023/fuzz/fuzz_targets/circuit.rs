@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc2023_day20b::circuit;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        // Pressing is left out, arbitrary wiring may loop forever
+        let _ = circuit::parse(content);
+    }
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+use aoc2023_day7b::cards::{Hand, Rules};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(line) = std::str::from_utf8(data) {
+        let _ = Hand::parse(line, &Rules::standard());
+        let _ = Hand::parse(line, &Rules::jokers_wild());
+
+        // Custom rules are read from files as well
+        if let Ok(rules) = line.parse::<Rules>() {
+            let _ = Hand::parse("AKQJT 1", &rules);
+        }
+    }
+});
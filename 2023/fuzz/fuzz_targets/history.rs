@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc2023_day9b::sequence;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        let _ = sequence::run(content, 1);
+        let _ = sequence::run(content, -1);
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use aoc2023_day18b::lagoon::{self, Instruction};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        for read in [Instruction::plain, Instruction::hex] {
+            if let Ok(instructions) = lagoon::parse(content, read) {
+                let _ = lagoon::area(&instructions);
+            }
+        }
+    }
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc2023_day13b::reflection;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(patterns) = reflection::parse(content) {
+            for pattern in patterns {
+                let _ = pattern.reflection(0);
+                let _ = pattern.reflection(1);
+            }
+        }
+    }
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc2023_day6b::race;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(races) = race::parse(content) {
+            let _ = race::product(&races);
+        }
+        if let Ok(race) = race::parse_kerned(content) {
+            let _ = race.ways();
+        }
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use aoc2023_day12b::record;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        let _ = record::parse(content);
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use aoc2023_day1b::vocabulary::Vocabulary;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        let _ = content.parse::<Vocabulary>();
+        let _ = Vocabulary::english().value(content);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc2023_day25a::graph;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(content) = std::str::from_utf8(data) {
+        if let Ok(graph) = graph::parse(content) {
+            let _ = graph.min_cut();
+        }
+    }
+});
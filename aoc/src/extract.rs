@@ -0,0 +1,168 @@
+// Example inputs and answers from a saved puzzle description. Every part of the puzzle
+// is an `<article>`, its example is the first `<pre><code>` block and its answer the
+// last emphasized code, as in "the sum is <code><em>142</em></code>". A part without a
+// block of its own reuses the example of the part before.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answer: Option<String>,
+    // Further blocks of the same part, which usually show intermediate steps
+    pub skipped: usize,
+}
+
+pub fn extract(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+
+    for article in sections(html, "<article", "</article>") {
+        let blocks: Vec<String> = sections(article, "<pre><code>", "</code></pre>")
+            .into_iter()
+            .map(text)
+            .collect();
+        let answer = emphasized(article).pop();
+
+        let example = match blocks.first() {
+            Some(input) => Example {
+                input: input.clone(),
+                answer,
+                skipped: blocks.len() - 1,
+            },
+            None => match examples.last() {
+                Some(previous) => Example {
+                    answer,
+                    ..previous.clone()
+                },
+                None => continue,
+            },
+        };
+        examples.push(example);
+    }
+
+    examples
+}
+
+// Contents between every start and the following end marker. A start marker may be
+// the beginning of a tag with attributes, whose rest is skipped.
+fn sections<'a>(html: &'a str, start: &str, end: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = html;
+
+    while let Some(i) = rest.find(start) {
+        rest = &rest[i + start.len()..];
+        if !start.ends_with('>') {
+            match rest.find('>') {
+                Some(close) => rest = &rest[close + 1..],
+                None => break,
+            }
+        }
+
+        match rest.find(end) {
+            Some(j) => {
+                found.push(&rest[..j]);
+                rest = &rest[j + end.len()..];
+            }
+            None => break,
+        }
+    }
+
+    found
+}
+
+// Texts of `<code><em>` and `<em><code>` pairs, in order of appearance
+fn emphasized(html: &str) -> Vec<String> {
+    let mut found: Vec<(usize, String)> = vec![];
+
+    for (start, end) in [
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ] {
+        let mut offset = 0;
+        while let Some(i) = html[offset..].find(start) {
+            let from = offset + i + start.len();
+            let Some(j) = html[from..].find(end) else {
+                break;
+            };
+            found.push((from, text(&html[from..from + j])));
+            offset = from + j + end.len();
+        }
+    }
+
+    found.sort();
+    found.into_iter().map(|(_, text)| text).collect()
+}
+
+// Drops tags and decodes the entities puzzle pages use
+fn text(html: &str) -> String {
+    let mut plain = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+
+    plain
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+</code></pre>
+<p>In this example, the calibration values of these four lines are <code>12</code>,
+<code>38</code>, <code>15</code>, and <code>77</code>. Adding these together produces
+<code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54953</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Digits may be <em>spelled out</em>, <code>a &lt; b</code>:</p>
+<pre><code>two1nine
+<em>eight</em>wothree
+</code></pre>
+<pre><code>ignored</code></pre>
+<p>Adding these together produces <em><code>281</code></em>.</p>
+</article>
+<article class="day-desc"><p>Same example, <code><em>7</em></code>.</p></article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_extract() {
+        let examples = extract(PAGE);
+        assert_eq!(examples.len(), 3);
+
+        assert_eq!(
+            examples[0].input,
+            "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n"
+        );
+        assert_eq!(examples[0].answer.as_deref(), Some("142"));
+        assert_eq!(examples[0].skipped, 0);
+
+        assert_eq!(examples[1].input, "two1nine\neightwothree\n");
+        assert_eq!(examples[1].answer.as_deref(), Some("281"));
+        assert_eq!(examples[1].skipped, 1);
+
+        assert_eq!(examples[2].input, examples[1].input);
+        assert_eq!(examples[2].answer.as_deref(), Some("7"));
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(text("<em>a</em> &lt;&amp;&gt; &amp;lt;"), "a <&> &lt;");
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Error};
+
+use crate::registry::{Key, Solution};
+
+// Puzzle inputs under `inputs/<year>/day<day>`, shared by both parts of a day. A part
+// with an input of its own, `inputs/<year>/day<day><part>`, uses that one instead.
+// Crates that still carry their input as `long_data` or `data` need no entry in the store.
+#[derive(Debug)]
+pub struct Inputs {
+    root: PathBuf,
+}
+
+impl Inputs {
+    pub fn new(root: &Path) -> Self {
+        Inputs {
+            root: root.join("inputs"),
+        }
+    }
+
+    pub fn day_path(&self, key: Key) -> PathBuf {
+        self.root
+            .join(key.year.to_string())
+            .join(format!("day{}", key.day))
+    }
+
+    pub fn part_path(&self, key: Key) -> PathBuf {
+        self.root
+            .join(key.year.to_string())
+            .join(format!("day{}{}", key.day, key.part.letter()))
+    }
+
+    pub fn find(&self, solution: &Solution) -> Result<PathBuf, Error> {
+        let candidates = [
+            self.part_path(solution.key),
+            self.day_path(solution.key),
+            solution.dir.join("long_data"),
+            solution.dir.join("data"),
+        ];
+
+        candidates
+            .iter()
+            .find(|path| path.is_file())
+            .cloned()
+            .ok_or(anyhow!(
+                "no input for {}, expected {}",
+                solution.key,
+                candidates[1].display()
+            ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_paths() {
+        let inputs = Inputs::new(Path::new("/repo"));
+        let key = "2024/3b".parse().unwrap();
+        assert_eq!(inputs.day_path(key), Path::new("/repo/inputs/2024/day3"));
+        assert_eq!(inputs.part_path(key), Path::new("/repo/inputs/2024/day3b"));
+    }
+}
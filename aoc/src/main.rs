@@ -0,0 +1,334 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Error};
+use clap::{Parser, Subcommand};
+
+mod extract;
+mod inputs;
+mod manifest;
+mod registry;
+mod runner;
+mod scaffold;
+mod submit;
+
+use inputs::Inputs;
+use manifest::Manifest;
+use registry::{Key, Part, Registry, Solution};
+use submit::{Attempt, Client, History, Verdict};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Repository root holding one directory per year
+    #[arg(short, long, default_value = ".")]
+    root: PathBuf,
+
+    /// File of known answers, relative to the root
+    #[arg(short, long, default_value = "answers")]
+    answers: PathBuf,
+
+    /// File of answers to the examples of the puzzle texts, relative to the root
+    #[arg(long, default_value = "examples")]
+    examples: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List the registered solutions with their known answers
+    List { year: Option<u16> },
+    /// Run solutions on their inputs and compare with the known answers
+    Run {
+        year: Option<u16>,
+        day: Option<u8>,
+        part: Option<Part>,
+
+        /// Run the alternative implementations as well
+        #[arg(short, long)]
+        variants: bool,
+
+        /// Store answers of keys that have none yet
+        #[arg(long)]
+        record: bool,
+
+        /// Run on `short_data` and compare with the answers to the examples
+        #[arg(short, long)]
+        examples: bool,
+    },
+    /// Create the crate of a new day and register it in the answers
+    New {
+        year: u16,
+        day: u8,
+        #[arg(default_value = "a")]
+        part: Part,
+
+        /// Saved puzzle description to take the example from
+        #[arg(short, long)]
+        page: Option<PathBuf>,
+    },
+    /// Take the examples of a saved puzzle description as `short_data` and `short_answer` of
+    /// its crates
+    Extract {
+        page: PathBuf,
+        year: u16,
+        day: u8,
+
+        /// Replace existing fixtures and example answers
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Submit an answer, unless the history shows it cannot be right
+    Submit {
+        year: u16,
+        day: u8,
+        part: Part,
+        #[arg(value_parser = submit::answer)]
+        answer: String,
+
+        /// Site to post to, a local stand-in for testing
+        #[arg(short, long, default_value = "https://adventofcode.com")]
+        endpoint: String,
+
+        /// File of past submissions, relative to the root
+        #[arg(long, default_value = "history")]
+        history: PathBuf,
+    },
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let answers = args.root.join(&args.answers);
+    let mut manifest = Manifest::load(&answers)?;
+    let examples = args.root.join(&args.examples);
+    let mut example_manifest = Manifest::load(&examples)?;
+
+    match args.command {
+        Command::List { year } => {
+            let registry = Registry::discover(&args.root)?;
+            for solution in registry.select(year, None, None) {
+                let variants: Vec<&str> = registry
+                    .variants(solution.key)
+                    .filter_map(|v| v.variant.as_deref())
+                    .collect();
+                let line = format!(
+                    "{:<10} {:<20} {:<20} {}",
+                    solution.key,
+                    solution.package,
+                    manifest.answer(solution.key).unwrap_or("?"),
+                    variants.join(" ")
+                );
+                println!("{}", line.trim_end());
+            }
+        }
+        Command::Run {
+            year,
+            day,
+            part,
+            variants,
+            record,
+            examples: on_examples,
+        } => {
+            let registry = Registry::discover(&args.root)?;
+            let inputs = Inputs::new(&args.root);
+            let (manifest, path) = match on_examples {
+                true => (&mut example_manifest, &examples),
+                false => (&mut manifest, &answers),
+            };
+
+            let mut solutions: Vec<&Solution> = vec![];
+            for solution in registry.select(year, day, part) {
+                solutions.push(solution);
+                if variants {
+                    solutions.extend(registry.variants(solution.key));
+                }
+            }
+            if solutions.is_empty() {
+                return Err(anyhow!("no registered solution matches"));
+            }
+
+            let mut failures = 0;
+            for solution in solutions {
+                let input = match on_examples {
+                    true => Ok(solution.dir.join("short_data")),
+                    false => inputs.find(solution),
+                };
+                if !check(solution, input, manifest, record) {
+                    failures += 1;
+                }
+            }
+
+            if record {
+                manifest.save(path)?;
+            }
+            if failures > 0 {
+                return Err(anyhow!("{} solutions failed", failures));
+            }
+        }
+        Command::New {
+            year,
+            day,
+            part,
+            page,
+        } => {
+            let key = Key::new(year, day, part)?;
+            scaffold::scaffold(&args.root, key)?;
+            manifest.register(key);
+            manifest.save(&answers)?;
+            example_manifest.register(key);
+            println!(
+                "created {}",
+                Path::new(&args.root).join(key.dir()).display()
+            );
+
+            if let Some(page) = page {
+                extract_page(&args.root, &page, year, day, &mut example_manifest, false)?;
+            }
+            example_manifest.save(&examples)?;
+        }
+        Command::Extract {
+            page,
+            year,
+            day,
+            force,
+        } => {
+            extract_page(&args.root, &page, year, day, &mut example_manifest, force)?;
+            example_manifest.save(&examples)?;
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            endpoint,
+            history,
+        } => {
+            let key = Key::new(year, day, part)?;
+            let path = args.root.join(history);
+            let mut history = History::load(&path)?;
+
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            history.check(key, &answer, now)?;
+
+            // The session cookie of the site, from the environment or a file kept out of git
+            let session = match env::var("AOC_SESSION") {
+                Ok(session) => session,
+                Err(_) => fs::read_to_string(args.root.join(".session"))
+                    .map_err(|_| anyhow!("no session, set AOC_SESSION or write .session"))?,
+            };
+
+            let verdict = Client::new(&endpoint, &session).submit(key, &answer)?;
+            history.record(
+                &path,
+                Attempt {
+                    time: now,
+                    key,
+                    answer: answer.clone(),
+                    verdict,
+                },
+            )?;
+            println!("{} {}: {}", key, answer, verdict);
+
+            if verdict == Verdict::Right {
+                manifest.insert(key, &answer);
+                manifest.save(&answers)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Writes the examples of a puzzle page into the crates of its parts that exist, the input
+// as `short_data` and the answer of the examples manifest as `short_answer`. Fixtures
+// typed by hand and known answers are kept unless forced, empty fixtures are not.
+fn extract_page(
+    root: &Path,
+    page: &Path,
+    year: u16,
+    day: u8,
+    examples: &mut Manifest,
+    force: bool,
+) -> Result<(), Error> {
+    let found = extract::extract(&fs::read_to_string(page)?);
+    if found.is_empty() {
+        return Err(anyhow!("{} holds no puzzle description", page.display()));
+    }
+
+    for (example, part) in found.iter().zip([Part::A, Part::B]) {
+        let key = Key::new(year, day, part)?;
+
+        match &example.answer {
+            Some(answer) if force || examples.answer(key).is_none() => examples.insert(key, answer),
+            Some(_) => println!("{}: keeping the known example answer", key),
+            None => println!("{}: the example has no emphasized answer", key),
+        }
+        if example.skipped > 0 {
+            println!("{}: skipped {} further code blocks", key, example.skipped);
+        }
+
+        let dir = root.join(key.dir());
+        if !dir.is_dir() {
+            println!("{}: no crate yet, skipped the fixture", key);
+            continue;
+        }
+
+        let fixture = dir.join("short_data");
+        let typed = fs::metadata(&fixture).is_ok_and(|m| m.len() > 0);
+        if typed && !force {
+            println!("{}: keeping {}", key, fixture.display());
+        } else {
+            fs::write(&fixture, &example.input)?;
+            println!("{}: wrote {}", key, fixture.display());
+        }
+
+        // Copied from the examples manifest, so the crate tests the answer it keeps
+        if let Some(answer) = examples.answer(key) {
+            fs::write(dir.join("short_answer"), format!("{}\n", answer))?;
+        }
+    }
+
+    Ok(())
+}
+
+// Runs one solution and prints how it went, false if it failed or answered wrong
+fn check(
+    solution: &Solution,
+    input: Result<PathBuf, Error>,
+    manifest: &mut Manifest,
+    record: bool,
+) -> bool {
+    let name = match &solution.variant {
+        Some(variant) => format!("{} ({})", solution.key, variant),
+        None => solution.key.to_string(),
+    };
+
+    let outcome = match input.and_then(|input| runner::run(solution, &input)) {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            println!("{:<16} error: {}", name, err);
+            return false;
+        }
+    };
+
+    let verdict = match manifest.answer(solution.key) {
+        Some(expected) if expected == outcome.answer => "ok".to_string(),
+        Some(expected) => format!("wrong, expected {}", expected),
+        None if record && solution.variant.is_none() => {
+            manifest.insert(solution.key, &outcome.answer);
+            "recorded".to_string()
+        }
+        None => "unknown".to_string(),
+    };
+    println!(
+        "{:<16} {:<20} {:>8.2?} {}",
+        name, outcome.answer, outcome.elapsed, verdict
+    );
+
+    !verdict.starts_with("wrong")
+}
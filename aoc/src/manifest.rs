@@ -0,0 +1,122 @@
+use std::{collections::BTreeMap, fmt, fs, path::Path, str::FromStr};
+
+use anyhow::{anyhow, Error};
+
+use crate::registry::Key;
+
+// Known answers as `2023/5b = 79874951` lines, a key without a value is not answered yet
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    answers: BTreeMap<Key, Option<String>>,
+}
+
+impl Manifest {
+    // A missing file is an empty manifest
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(Manifest::default());
+        }
+        fs::read_to_string(path)?.parse()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        Ok(fs::write(path, self.to_string())?)
+    }
+
+    pub fn answer(&self, key: Key) -> Option<&str> {
+        self.answers.get(&key)?.as_deref()
+    }
+
+    // Registers a key without overwriting a known answer
+    pub fn register(&mut self, key: Key) {
+        self.answers.entry(key).or_insert(None);
+    }
+
+    pub fn insert(&mut self, key: Key, answer: &str) {
+        self.answers.insert(key, Some(answer.to_string()));
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = BTreeMap::new();
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, answer) = line.split_once('=').ok_or(anyhow!(
+                "answer should have the form key = answer: {}",
+                line
+            ))?;
+            let key: Key = key.parse()?;
+            let answer = Some(answer.trim())
+                .filter(|a| !a.is_empty())
+                .map(str::to_string);
+
+            if answers.insert(key, answer).is_some() {
+                return Err(anyhow!("{} is listed twice", key));
+            }
+        }
+
+        Ok(Manifest { answers })
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# year/day part = answer, keys without an answer are not solved yet"
+        )?;
+        for (key, answer) in &self.answers {
+            match answer {
+                Some(answer) => writeln!(f, "{} = {}", key, answer)?,
+                None => writeln!(f, "{} =", key)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let manifest: Manifest = "
+            # answers
+            2023/5b = 79874951
+            2022/1a = 24000
+            2024/3a =
+        "
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            manifest.answer("2023/5b".parse().unwrap()),
+            Some("79874951")
+        );
+        assert_eq!(manifest.answer("2024/3a".parse().unwrap()), None);
+
+        let text = manifest.to_string();
+        assert!(text.contains("2024/3a =\n"));
+        assert!(text.find("2022/1a").unwrap() < text.find("2023/5b").unwrap());
+        assert_eq!(text.parse::<Manifest>().unwrap(), manifest);
+
+        assert!("2023/5b = 1\n2023/5b = 2".parse::<Manifest>().is_err());
+    }
+
+    #[test]
+    fn test_register() {
+        let key = "2023/5b".parse().unwrap();
+        let mut manifest = Manifest::default();
+        manifest.insert(key, "46");
+        manifest.register(key);
+        assert_eq!(manifest.answer(key), Some("46"));
+    }
+}
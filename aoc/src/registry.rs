@@ -0,0 +1,257 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    pub fn letter(&self) -> char {
+        match self {
+            Part::A => 'a',
+            Part::B => 'b',
+        }
+    }
+}
+
+impl TryFrom<char> for Part {
+    type Error = Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'a' => Ok(Part::A),
+            'b' => Ok(Part::B),
+            _ => Err(anyhow!("part should be a or b: {}", value)),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.chars().collect::<Vec<_>>()[..] {
+            [c] => Part::try_from(c),
+            _ => Err(anyhow!("part should be a or b: {}", s)),
+        }
+    }
+}
+
+// Identifies one puzzle answer across all years, written as `2023/5b`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Key {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+}
+
+impl Key {
+    pub fn new(year: u16, day: u8, part: Part) -> Result<Self, Error> {
+        if !(2015..=9999).contains(&year) {
+            return Err(anyhow!("there is no advent of code {}", year));
+        }
+        if !(1..=25).contains(&day) {
+            return Err(anyhow!("day should be between 1 and 25: {}", day));
+        }
+
+        Ok(Key { year, day, part })
+    }
+
+    // Directory of the crate solving the key, relative to the repository root
+    pub fn dir(&self) -> PathBuf {
+        Path::new(&self.year.to_string()).join(format!("day{}{}", self.day, self.part.letter()))
+    }
+
+    // Package names carry the year, so crates of different years never collide
+    pub fn package(&self, variant: Option<&str>) -> String {
+        let name = format!("aoc{}-day{}{}", self.year, self.day, self.part.letter());
+        match variant {
+            Some(variant) => format!("{}-{}", name, variant.replace('_', "-")),
+            None => name,
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!("{}/{}{}", self.year, self.day, self.part.letter()))
+    }
+}
+
+impl FromStr for Key {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || {
+            anyhow!(
+                "key should have the form year/day part, like 2023/5b: {}",
+                s
+            )
+        };
+
+        let (year, day) = s.trim().split_once('/').ok_or_else(error)?;
+        let part = day.chars().last().ok_or_else(error)?;
+        let day = day[..day.len() - part.len_utf8()]
+            .parse()
+            .map_err(|_| error())?;
+
+        Key::new(
+            year.parse().map_err(|_| error())?,
+            day,
+            Part::try_from(part)?,
+        )
+    }
+}
+
+// A crate solving one part of one day. Variants are alternative implementations kept
+// next to the main one, like `day12b_old`.
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub key: Key,
+    pub variant: Option<String>,
+    pub dir: PathBuf,
+    pub package: String,
+}
+
+// All solutions found below the repository root. A crate registers itself by living in
+// `<year>/day<day><part>[_variant]`, nothing has to be listed by hand.
+#[derive(Debug, Default)]
+pub struct Registry {
+    solutions: Vec<Solution>,
+}
+
+impl Registry {
+    pub fn discover(root: &Path) -> Result<Self, Error> {
+        let mut solutions = vec![];
+
+        for year in fs::read_dir(root)? {
+            let year = year?;
+            let Some(number) = year
+                .file_name()
+                .to_str()
+                .and_then(|y| y.parse::<u16>().ok())
+            else {
+                continue;
+            };
+
+            for day in fs::read_dir(year.path())? {
+                let day = day?;
+                let manifest = day.path().join("Cargo.toml");
+                let name = day.file_name();
+                let Some((n, part, variant)) = name.to_str().and_then(parse_dir_name) else {
+                    continue;
+                };
+                if !manifest.is_file() {
+                    continue;
+                }
+
+                solutions.push(Solution {
+                    key: Key::new(number, n, part)?,
+                    variant,
+                    dir: day.path(),
+                    package: package_name(&fs::read_to_string(&manifest)?)
+                        .ok_or(anyhow!("{} has no package name", manifest.display()))?,
+                });
+            }
+        }
+
+        solutions.sort_by(|a, b| (a.key, &a.variant).cmp(&(b.key, &b.variant)));
+        Ok(Registry { solutions })
+    }
+
+    pub fn variants(&self, key: Key) -> impl Iterator<Item = &Solution> {
+        self.solutions
+            .iter()
+            .filter(move |s| s.key == key && s.variant.is_some())
+    }
+
+    // Main solutions matching every given component of a key
+    pub fn select(
+        &self,
+        year: Option<u16>,
+        day: Option<u8>,
+        part: Option<Part>,
+    ) -> impl Iterator<Item = &Solution> {
+        self.solutions.iter().filter(move |s| {
+            s.variant.is_none()
+                && year.is_none_or(|year| s.key.year == year)
+                && day.is_none_or(|day| s.key.day == day)
+                && part.is_none_or(|part| s.key.part == part)
+        })
+    }
+}
+
+// `day12b_old` is day 12, part b, variant `old`
+pub fn parse_dir_name(name: &str) -> Option<(u8, Part, Option<String>)> {
+    let rest = name.strip_prefix("day")?;
+    let (name, variant) = match rest.split_once('_') {
+        Some((name, variant)) => (name, Some(variant.to_string())),
+        None => (rest, None),
+    };
+
+    let part = Part::try_from(name.chars().last()?).ok()?;
+    let day = name[..name.len() - 1].parse().ok()?;
+    Some((day, part, variant))
+}
+
+// Name of the package in a Cargo.toml, good enough for the manifests of this repository
+fn package_name(manifest: &str) -> Option<String> {
+    let mut in_package = false;
+
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if let Some(value) = line.strip_prefix("name") {
+            if in_package {
+                let value = value.trim().strip_prefix('=')?.trim();
+                return Some(value.trim_matches('"').to_string());
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_keys() {
+        let key: Key = "2023/12b".parse().unwrap();
+        assert_eq!(key, Key::new(2023, 12, Part::B).unwrap());
+        assert_eq!(key.to_string(), "2023/12b");
+        assert_eq!(key.dir(), Path::new("2023/day12b"));
+        assert_eq!(key.package(None), "aoc2023-day12b");
+        assert_eq!(key.package(Some("old")), "aoc2023-day12b-old");
+
+        assert!("2023/26a".parse::<Key>().is_err());
+        assert!("2023/5c".parse::<Key>().is_err());
+        assert!("2023-5a".parse::<Key>().is_err());
+    }
+
+    #[test]
+    fn test_dir_names() {
+        assert_eq!(parse_dir_name("day5b"), Some((5, Part::B, None)));
+        assert_eq!(
+            parse_dir_name("day5b_prime"),
+            Some((5, Part::B, Some("prime".to_string())))
+        );
+        assert_eq!(parse_dir_name("day5"), None);
+        assert_eq!(parse_dir_name("target"), None);
+    }
+
+    #[test]
+    fn test_package_name() {
+        let manifest = "[package]\nname = \"aoc2023-day1a\"\n\n[dependencies]\nname = \"x\"\n";
+        assert_eq!(package_name(manifest).unwrap(), "aoc2023-day1a");
+        assert_eq!(package_name("[dependencies]\nname = \"x\""), None);
+    }
+}
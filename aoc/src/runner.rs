@@ -0,0 +1,105 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Error};
+use serde_json::Value;
+
+use crate::registry::Solution;
+
+#[derive(Debug)]
+pub struct Outcome {
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+// Builds a solution through cargo and runs its binary on an input, only the run itself is
+// timed. The answer is the last word the solution prints, which also covers `result: 42`.
+pub fn run(solution: &Solution, input: &Path) -> Result<Outcome, Error> {
+    let input = input.canonicalize()?;
+    let dir = solution.dir.canonicalize()?;
+
+    let binary = build(solution, &dir)?;
+
+    let start = Instant::now();
+    let output = Command::new(&binary)
+        .arg("--file")
+        .arg(&input)
+        .current_dir(&dir)
+        .output()?;
+    let elapsed = start.elapsed();
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "{} failed: {}",
+            solution.package,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let answer = answer(&String::from_utf8_lossy(&output.stdout))
+        .ok_or(anyhow!("{} printed no answer", solution.package))?;
+    Ok(Outcome { answer, elapsed })
+}
+
+// Path of the release binary, as reported by cargo wherever its target directory is
+fn build(solution: &Solution, dir: &Path) -> Result<PathBuf, Error> {
+    let build = Command::new("cargo")
+        .args(["build", "--quiet", "--release", "--message-format=json"])
+        .arg("--manifest-path")
+        .arg(dir.join("Cargo.toml"))
+        .output()?;
+    if !build.status.success() {
+        return Err(anyhow!(
+            "{} does not build: {}",
+            solution.package,
+            String::from_utf8_lossy(&build.stderr).trim()
+        ));
+    }
+
+    executable(&String::from_utf8_lossy(&build.stdout), &solution.package)
+        .ok_or(anyhow!("{} builds no binary", solution.package))
+}
+
+fn executable(messages: &str, package: &str) -> Option<PathBuf> {
+    messages
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-artifact")
+        .filter(|message| message["target"]["name"] == package)
+        .find_map(|message| message["executable"].as_str().map(PathBuf::from))
+}
+
+fn answer(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .rev()
+        .find_map(|line| line.split_whitespace().last())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(answer("42\n").unwrap(), "42");
+        assert_eq!(answer("explanation\nresult: 42\n\n").unwrap(), "42");
+        assert_eq!(answer("\n \n"), None);
+    }
+
+    #[test]
+    fn test_executable() {
+        let messages = r#"{"reason":"compiler-artifact","target":{"name":"aoc2023_day5b"},"executable":null}
+{"reason":"compiler-artifact","target":{"name":"aoc2023-day5b"},"executable":"/t/release/aoc2023-day5b"}
+{"reason":"build-finished","success":true}"#;
+        assert_eq!(
+            executable(messages, "aoc2023-day5b").unwrap(),
+            PathBuf::from("/t/release/aoc2023-day5b")
+        );
+        assert_eq!(executable(messages, "aoc2023-day5b-prime"), None);
+    }
+}
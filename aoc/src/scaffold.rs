@@ -0,0 +1,53 @@
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, Error};
+
+use crate::registry::Key;
+
+const CARGO: &str = include_str!("../templates/Cargo.toml");
+const MAIN: &str = include_str!("../templates/main.rs");
+const PUZZLE: &str = include_str!("../templates/puzzle.rs");
+
+// Creates the crate of a key below the repository root, with a parser module, an empty
+// `short_data` and `short_answer` for the example and a test against them
+pub fn scaffold(root: &Path, key: Key) -> Result<(), Error> {
+    let dir = root.join(key.dir());
+    if dir.exists() {
+        return Err(anyhow!("{} already exists", dir.display()));
+    }
+
+    let fill = |template: &str| {
+        template
+            .replace("{package}", &key.package(None))
+            .replace("{key}", &key.to_string())
+    };
+
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(dir.join("Cargo.toml"), fill(CARGO))?;
+    fs::write(dir.join("src/main.rs"), fill(MAIN))?;
+    fs::write(dir.join("src/puzzle.rs"), fill(PUZZLE))?;
+    fs::write(dir.join("short_data"), "")?;
+    fs::write(dir.join("short_answer"), "")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_scaffold() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let key = "2024/3a".parse().unwrap();
+
+        scaffold(&root, key).unwrap();
+        let cargo = fs::read_to_string(root.join("2024/day3a/Cargo.toml")).unwrap();
+        assert!(cargo.contains("name = \"aoc2024-day3a\""));
+        assert!(root.join("2024/day3a/src/puzzle.rs").is_file());
+        assert!(root.join("2024/day3a/short_answer").is_file());
+        assert!(scaffold(&root, key).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
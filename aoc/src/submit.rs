@@ -0,0 +1,411 @@
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Error};
+
+use crate::registry::{Key, Part};
+
+// Seconds the site makes us wait after a wrong answer
+const WRONG_COOLDOWN: u64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // Submitted too recently, seconds left to wait
+    Wait(u64),
+    // The part is already solved or not unlocked yet
+    WrongLevel,
+}
+
+impl Verdict {
+    // Reads the verdict out of the page the site answers a submission with
+    pub fn parse(page: &str) -> Option<Verdict> {
+        if page.contains("That's the right answer") {
+            Some(Verdict::Right)
+        } else if page.contains("That's not the right answer") {
+            if page.contains("too high") {
+                Some(Verdict::TooHigh)
+            } else if page.contains("too low") {
+                Some(Verdict::TooLow)
+            } else {
+                Some(Verdict::Wrong)
+            }
+        } else if page.contains("You gave an answer too recently") {
+            let (before, _) = page.split_once("left to wait")?;
+            Some(Verdict::Wait(wait_seconds(before)))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+// Sums the `1m 5s` like durations right before the end of the text
+fn wait_seconds(text: &str) -> u64 {
+    let mut seconds = 0;
+
+    for word in text.split_whitespace().rev() {
+        let (number, factor) = match word.chars().last() {
+            Some('s') => (&word[..word.len() - 1], 1),
+            Some('m') => (&word[..word.len() - 1], 60),
+            Some('h') => (&word[..word.len() - 1], 3600),
+            _ => break,
+        };
+        match number.parse::<u64>() {
+            Ok(number) => seconds += number * factor,
+            Err(_) => break,
+        }
+    }
+
+    seconds
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "right"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Wait(seconds) => write!(f, "wait-{}", seconds),
+            Verdict::WrongLevel => write!(f, "wrong-level"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "right" => Ok(Verdict::Right),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wrong-level" => Ok(Verdict::WrongLevel),
+            _ => s
+                .strip_prefix("wait-")
+                .and_then(|seconds| seconds.parse().ok())
+                .map(Verdict::Wait)
+                .ok_or(anyhow!("unknown verdict: {}", s)),
+        }
+    }
+}
+
+// One submission, kept as `time key answer verdict` with the time in unix seconds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub time: u64,
+    pub key: Key,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.time, self.key, self.answer, self.verdict
+        )
+    }
+}
+
+// Trims an answer before it is posted. Answers are single words, as one with whitespace
+// would not read back from its line in the history.
+pub fn answer(s: &str) -> Result<String, Error> {
+    let answer = s.trim();
+    if answer.is_empty() {
+        return Err(anyhow!("answer should not be empty"));
+    }
+    if answer.contains(char::is_whitespace) {
+        return Err(anyhow!("answer should not contain whitespace: {}", answer));
+    }
+    Ok(answer.to_string())
+}
+
+impl FromStr for Attempt {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_whitespace().collect::<Vec<_>>()[..] {
+            [time, key, answer, verdict] => Ok(Attempt {
+                time: time.parse()?,
+                key: key.parse()?,
+                answer: answer.to_string(),
+                verdict: verdict.parse()?,
+            }),
+            _ => Err(anyhow!(
+                "attempt should have the form time key answer verdict: {}",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    attempts: Vec<Attempt>,
+}
+
+impl History {
+    // A missing file is an empty history
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        if !path.exists() {
+            return Ok(History::default());
+        }
+
+        let attempts = fs::read_to_string(path)?
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+        Ok(History { attempts })
+    }
+
+    // Adds an attempt and appends it to the file, so no attempt is lost on a crash
+    pub fn record(&mut self, path: &Path, attempt: Attempt) -> Result<(), Error> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", attempt)?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    pub fn attempts(&self, key: Key) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |a| a.key == key)
+    }
+
+    // Refuses answers the site would reject anyway: once a part is solved, an answer
+    // already known wrong, a number outside the bounds given by earlier too high and
+    // too low verdicts, and anything while the site still wants us to wait
+    pub fn check(&self, key: Key, answer: &str, now: u64) -> Result<(), Error> {
+        if let Some(right) = self.attempts(key).find(|a| a.verdict == Verdict::Right) {
+            return Err(anyhow!("{} is already solved with {}", key, right.answer));
+        }
+
+        if let Some(wrong) = self
+            .attempts(key)
+            .find(|a| a.answer == answer && a.verdict.is_wrong())
+        {
+            return Err(anyhow!(
+                "{} was already submitted as {}",
+                answer,
+                wrong.verdict
+            ));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |verdict| {
+                self.attempts(key)
+                    .filter(move |a| a.verdict == verdict)
+                    .filter_map(|a| a.answer.parse::<i128>().ok())
+            };
+            if let Some(high) = bound(Verdict::TooHigh).filter(|high| value >= *high).min() {
+                return Err(anyhow!("{} is not below {}, known too high", value, high));
+            }
+            if let Some(low) = bound(Verdict::TooLow).filter(|low| value <= *low).max() {
+                return Err(anyhow!("{} is not above {}, known too low", value, low));
+            }
+        }
+
+        // The site limits submissions across all puzzles
+        let ready = self
+            .attempts
+            .iter()
+            .map(|a| match a.verdict {
+                Verdict::Wait(seconds) => a.time + seconds,
+                verdict if verdict.is_wrong() => a.time + WRONG_COOLDOWN,
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0);
+        if now < ready {
+            return Err(anyhow!("{}s left to wait", ready - now));
+        }
+
+        Ok(())
+    }
+}
+
+// Posts answers to the site, or to a local stand-in at another endpoint
+#[derive(Debug)]
+pub struct Client {
+    endpoint: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(endpoint: &str, session: &str) -> Self {
+        Client {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    pub fn submit(&self, key: Key, answer: &str) -> Result<Verdict, Error> {
+        let url = format!("{}/{}/day/{}/answer", self.endpoint, key.year, key.day);
+        let level = match key.part {
+            Part::A => "1",
+            Part::B => "2",
+        };
+
+        let page = ureq::post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", level), ("answer", answer)])?
+            .into_string()?;
+
+        Verdict::parse(&page).ok_or(anyhow!("{} answered with an unknown page", url))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{BufRead, BufReader, Read},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    fn attempt(s: &str) -> Attempt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_verdicts() {
+        let wrong = "<p>That's not the right answer; your answer is too high. \
+            Please wait one minute before trying again.</p>";
+        assert_eq!(Verdict::parse(wrong), Some(Verdict::TooHigh));
+        assert_eq!(
+            Verdict::parse("That's not the right answer. If you're stuck..."),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently; you have 1m 5s left to wait."),
+            Some(Verdict::Wait(65))
+        );
+        assert_eq!(
+            Verdict::parse("That's the right answer! You are one gold star closer."),
+            Some(Verdict::Right)
+        );
+        assert_eq!(Verdict::parse("<html>login</html>"), None);
+
+        for verdict in ["right", "too-low", "wait-65", "wrong-level"] {
+            assert_eq!(verdict.parse::<Verdict>().unwrap().to_string(), verdict);
+        }
+    }
+
+    #[test]
+    fn test_answers() {
+        assert!(answer("").is_err());
+        assert!(answer(" \n").is_err());
+        assert!(answer("12 34").is_err());
+
+        let attempt = Attempt {
+            time: 1000,
+            key: "2023/5b".parse().unwrap(),
+            answer: answer(" 46\n").unwrap(),
+            verdict: Verdict::Wrong,
+        };
+        assert_eq!(attempt.answer, "46");
+        assert_eq!(attempt.to_string().parse::<Attempt>().unwrap(), attempt);
+    }
+
+    #[test]
+    fn test_refusals() {
+        let key: Key = "2023/5b".parse().unwrap();
+        let history = History {
+            attempts: vec![
+                attempt("1000 2023/5b 500 too-high"),
+                attempt("1100 2023/5b 100 too-low"),
+                attempt("1200 2023/5b abc wrong"),
+                attempt("1300 2023/6a 7 wait-30"),
+            ],
+        };
+
+        assert!(history.check(key, "abc", 2000).is_err());
+        assert!(history.check(key, "500", 2000).is_err());
+        assert!(history.check(key, "600", 2000).is_err());
+        assert!(history.check(key, "100", 2000).is_err());
+        assert!(history.check(key, "250", 2000).is_ok());
+        assert!(history.check(key, "def", 2000).is_ok());
+
+        // Still within the cooldown of the wrong answer, then of the wait
+        assert!(history.check(key, "250", 1250).is_err());
+        assert!(history.check(key, "250", 1320).is_err());
+        assert!(history.check(key, "250", 1330).is_ok());
+
+        let solved = History {
+            attempts: vec![attempt("1000 2023/5b 250 right")],
+        };
+        assert!(solved.check(key, "251", 2000).is_err());
+    }
+
+    #[test]
+    fn test_stand_in() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut head = vec![];
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push(line.trim().to_string());
+            }
+            let length: usize = head
+                .iter()
+                .find_map(|h| {
+                    h.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(String::from)
+                })
+                .unwrap()
+                .trim()
+                .parse()
+                .unwrap();
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            let page = "<article><p>That's the right answer!</p></article>";
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                page.len(),
+                page
+            )
+            .unwrap();
+
+            (head, String::from_utf8(body).unwrap())
+        });
+
+        let client = Client::new(&endpoint, "secret\n");
+        let verdict = client.submit("2023/5b".parse().unwrap(), "46").unwrap();
+        assert_eq!(verdict, Verdict::Right);
+
+        let (head, body) = server.join().unwrap();
+        assert_eq!(head[0], "POST /2023/day/5/answer HTTP/1.1");
+        assert!(head.contains(&"Cookie: session=secret".to_string()));
+        assert_eq!(body, "level=2&answer=46");
+    }
+}
@@ -0,0 +1,75 @@
+#![feature(test)]
+
+extern crate test;
+
+use std::{fs::File, io::Read};
+
+use anyhow::Error;
+use clap::Parser;
+
+mod puzzle;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[arg(short, long)]
+    file: String,
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::parse();
+    let file = args.file;
+
+    let mut file = File::open(file)?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+
+    println!("{}", run(&content)?);
+
+    Ok(())
+}
+
+fn run(content: &str) -> Result<usize, Error> {
+    let lines = puzzle::parse(content)?;
+    Ok(lines.len())
+}
+
+// testing
+#[cfg(test)]
+mod tests {
+    use ::test::Bencher;
+
+    use super::*;
+
+    // Answer to the example, written next to `short_data` by aoc extract
+    fn example() -> String {
+        let answer = std::fs::read_to_string("short_answer").unwrap();
+        let answer = answer.trim();
+        assert!(
+            !answer.is_empty(),
+            "no example answer for {key}, extract it with aoc extract"
+        );
+        answer.to_string()
+    }
+
+    #[test]
+    fn test_short() {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        let result = run(&content).unwrap();
+        assert_eq!(result.to_string(), example())
+    }
+
+    #[bench]
+    fn bench(b: &mut Bencher) {
+        let file = "short_data";
+        let mut file = File::open(file).unwrap();
+        let mut content = String::new();
+        file.read_to_string(&mut content).unwrap();
+
+        b.iter(|| run(&content));
+    }
+}
@@ -0,0 +1,20 @@
+use anyhow::Error;
+
+// Input of {key}, one entry per non-empty line
+pub fn parse(content: &str) -> Result<Vec<String>, Error> {
+    Ok(content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("a\n\nb\n").unwrap(), vec!["a", "b"]);
+    }
+}